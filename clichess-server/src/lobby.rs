@@ -0,0 +1,357 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use actix::{Actor, AsyncContext, Context, Handler, Recipient};
+use chess::{CastlingRights, ChessBoard, ChessMove, Color};
+use uuid::Uuid;
+
+use crate::messages::{
+    ChessMessage, Connect, Disconnect, JoinOutcome, JoinRoomError, MakeMove, Seated, WsMessage,
+};
+
+/// How long a seat stays reserved after its socket drops before `Lobby` gives up on it
+/// reconnecting.
+const RECONNECT_GRACE_PERIOD: Duration = Duration::from_secs(60);
+/// How often `Lobby` sweeps every room for seats whose grace period has lapsed.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Whether a seat's socket is currently live, was never connected yet, or dropped and is still
+/// within its reconnect grace period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlayerStatus {
+    Waiting,
+    Connected,
+    Reconnecting,
+}
+
+/// One seated player: the persistent `token` a reconnect presents to reclaim this seat, and
+/// `addr`/`conn_id` for whichever socket currently holds it (`None` while `Reconnecting`).
+struct Seat {
+    token: Uuid,
+    conn_id: Option<Uuid>,
+    addr: Option<Recipient<WsMessage>>,
+    color: Color,
+    status: PlayerStatus,
+    disconnected_at: Option<Instant>,
+}
+
+impl Seat {
+    fn new(token: Uuid, conn_id: Uuid, addr: Recipient<WsMessage>, color: Color) -> Self {
+        Self {
+            token,
+            conn_id: Some(conn_id),
+            addr: Some(addr),
+            color,
+            status: PlayerStatus::Waiting,
+            disconnected_at: None,
+        }
+    }
+
+    fn send(&self, message: &str) {
+        if let Some(addr) = &self.addr {
+            let _ = addr.do_send(WsMessage(message.to_string()));
+        }
+    }
+
+    fn rebind(&mut self, conn_id: Uuid, addr: Recipient<WsMessage>) {
+        self.conn_id = Some(conn_id);
+        self.addr = Some(addr);
+        self.status = PlayerStatus::Connected;
+        self.disconnected_at = None;
+    }
+
+    fn mark_disconnected(&mut self) {
+        self.conn_id = None;
+        self.addr = None;
+        self.status = PlayerStatus::Reconnecting;
+        self.disconnected_at = Some(Instant::now());
+    }
+}
+
+/// A connection watching a room without holding a seat in it: no token, no color, no turn, just
+/// the broadcasts.
+struct Spectator {
+    conn_id: Uuid,
+    addr: Recipient<WsMessage>,
+}
+
+impl Spectator {
+    fn new(conn_id: Uuid, addr: Recipient<WsMessage>) -> Self {
+        Self { conn_id, addr }
+    }
+
+    fn send(&self, message: &str) {
+        let _ = self.addr.do_send(WsMessage(message.to_string()));
+    }
+}
+
+/// A single in-progress game, its (up to two) seated players, and whoever else is watching.
+struct Room {
+    board: ChessBoard,
+    side_to_move: Color,
+    /// How many moves have been played so far; only used to answer "has this room's game
+    /// started", the same thing `!Game::history().is_empty()` would tell a `Game`-backed room.
+    moves_played: u32,
+    seats: Vec<Seat>,
+    spectators: Vec<Spectator>,
+}
+
+impl Room {
+    fn new(first: Seat) -> Self {
+        Self {
+            board: ChessBoard::starting_position(),
+            side_to_move: Color::White,
+            moves_played: 0,
+            seats: vec![first],
+            spectators: Vec::new(),
+        }
+    }
+
+    fn seat_by_conn(&self, conn_id: Uuid) -> Option<&Seat> {
+        self.seats.iter().find(|seat| seat.conn_id == Some(conn_id))
+    }
+
+    fn seat_by_conn_mut(&mut self, conn_id: Uuid) -> Option<&mut Seat> {
+        self.seats
+            .iter_mut()
+            .find(|seat| seat.conn_id == Some(conn_id))
+    }
+
+    fn seat_by_token_mut(&mut self, token: Uuid) -> Option<&mut Seat> {
+        self.seats.iter_mut().find(|seat| seat.token == token)
+    }
+
+    fn spectator_by_conn(&self, conn_id: Uuid) -> Option<&Spectator> {
+        self.spectators
+            .iter()
+            .find(|spectator| spectator.conn_id == conn_id)
+    }
+
+    fn broadcast(&self, message: &str) {
+        for seat in &self.seats {
+            seat.send(message);
+        }
+        for spectator in &self.spectators {
+            spectator.send(message);
+        }
+    }
+
+    /// Sends every seat and spectator a `ChessMessage::BoardState` for the room's current
+    /// position, serialized as JSON text on the same `WsMessage` channel everything else uses.
+    fn relay_board(&self) {
+        let state = ChessMessage::BoardState { fen: self.fen() };
+        let payload =
+            serde_json::to_string(&state).expect("ChessMessage::BoardState always serializes");
+        self.broadcast(&payload);
+    }
+
+    /// The room's board as the piece-placement and side-to-move fields of FEN; see
+    /// `ChessMessage::BoardState`'s doc comment for why that's shorter than a full FEN string.
+    fn fen(&self) -> String {
+        let side = match self.side_to_move {
+            Color::White => "w",
+            Color::Black => "b",
+        };
+        format!("{} {}", self.board.to_fen(), side)
+    }
+
+    /// A room only expires once every seat has been vacant past the grace period; a single
+    /// player dropping mid-game should never end the game for the opponent who's still there.
+    /// Spectators don't keep a room alive on their own: they're not tracked here at all, so a
+    /// room with no seated players left expires on schedule even with spectators still watching.
+    fn is_expired(&self, now: Instant) -> bool {
+        !self.seats.is_empty()
+            && self.seats.iter().all(|seat| {
+                seat.status == PlayerStatus::Reconnecting
+                    && seat
+                        .disconnected_at
+                        .map(|since| now.duration_since(since) > RECONNECT_GRACE_PERIOD)
+                        .unwrap_or(false)
+            })
+    }
+}
+
+/// The room registry: one actor owning every in-progress game, so every `Connect`,
+/// `Disconnect`, and move comes through a single mailbox and the `HashMap<RoomId, Room>` never
+/// needs a lock of its own.
+#[derive(Default)]
+pub struct Lobby {
+    rooms: HashMap<String, Room>,
+}
+
+impl Actor for Lobby {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.run_interval(SWEEP_INTERVAL, |lobby, _ctx| {
+            let now = Instant::now();
+            lobby.rooms.retain(|_, room| !room.is_expired(now));
+        });
+    }
+}
+
+impl Handler<Connect> for Lobby {
+    type Result = Result<JoinOutcome, JoinRoomError>;
+
+    fn handle(&mut self, msg: Connect, _ctx: &mut Context<Self>) -> Self::Result {
+        if let Some(token) = msg.token {
+            let room = self
+                .rooms
+                .get_mut(&msg.room_id)
+                .ok_or(JoinRoomError::UnknownToken)?;
+            let seat = room
+                .seat_by_token_mut(token)
+                .ok_or(JoinRoomError::UnknownToken)?;
+
+            seat.rebind(msg.self_id, msg.addr);
+            let color = seat.color;
+            room.broadcast("opponent reconnected");
+            if let Some(seat) = room.seat_by_conn(msg.self_id) {
+                seat.send("reconnected, replaying board");
+            }
+            room.relay_board();
+            return Ok(JoinOutcome::Seated(Seated { color, token }));
+        }
+
+        match self.rooms.get_mut(&msg.room_id) {
+            Some(room) => {
+                if room.seats.len() >= 2 {
+                    room.spectators.push(Spectator::new(msg.self_id, msg.addr));
+                    if let Some(spectator) = room.spectator_by_conn(msg.self_id) {
+                        spectator.send("spectating, replaying board");
+                    }
+                    room.relay_board();
+                    return Ok(JoinOutcome::Spectating);
+                }
+                if room.moves_played > 0 {
+                    return Err(JoinRoomError::AlreadyStarted);
+                }
+
+                let color = room
+                    .seats
+                    .first()
+                    .map(|seat| seat.color.opponent())
+                    .unwrap_or(Color::White);
+                let token = Uuid::new_v4();
+                room.seats
+                    .push(Seat::new(token, msg.self_id, msg.addr, color));
+                for seat in &mut room.seats {
+                    seat.status = PlayerStatus::Connected;
+                }
+                room.broadcast("both players connected, white to move");
+                room.relay_board();
+                Ok(JoinOutcome::Seated(Seated { color, token }))
+            }
+            None => {
+                if !msg.create_if_missing {
+                    return Err(JoinRoomError::DoesntExist);
+                }
+
+                let token = Uuid::new_v4();
+                let seat = Seat::new(token, msg.self_id, msg.addr, Color::White);
+                self.rooms.insert(msg.room_id, Room::new(seat));
+                Ok(JoinOutcome::Seated(Seated {
+                    color: Color::White,
+                    token,
+                }))
+            }
+        }
+    }
+}
+
+impl Handler<Disconnect> for Lobby {
+    type Result = ();
+
+    fn handle(&mut self, msg: Disconnect, _ctx: &mut Context<Self>) {
+        if let Some(room) = self.rooms.get_mut(&msg.room_id) {
+            if room.seat_by_conn(msg.id).is_some() {
+                if let Some(seat) = room.seat_by_conn_mut(msg.id) {
+                    seat.mark_disconnected();
+                }
+                room.broadcast("opponent disconnected, waiting for them to reconnect");
+                return;
+            }
+
+            // Spectators have no seat to keep warm for a reconnect; they just drop off the
+            // broadcast list.
+            room.spectators
+                .retain(|spectator| spectator.conn_id != msg.id);
+        }
+    }
+}
+
+impl Handler<MakeMove> for Lobby {
+    type Result = ();
+
+    fn handle(&mut self, msg: MakeMove, _ctx: &mut Context<Self>) {
+        let room = match self.rooms.get_mut(&msg.room_id) {
+            Some(room) => room,
+            None => return,
+        };
+
+        let seat_color = match room.seat_by_conn(msg.id) {
+            Some(seat) => seat.color,
+            None => {
+                if let Some(spectator) = room.spectator_by_conn(msg.id) {
+                    spectator.send("spectators can't make moves");
+                }
+                return;
+            }
+        };
+
+        if seat_color != room.side_to_move {
+            if let Some(seat) = room.seat_by_conn(msg.id) {
+                seat.send("it's not your turn");
+            }
+            return;
+        }
+
+        let chess_move = match resolve_move(&room.board, room.side_to_move, &msg) {
+            Some(chess_move) => chess_move,
+            None => {
+                if let Some(seat) = room.seat_by_conn(msg.id) {
+                    seat.send(&format!("illegal move: {}{}", msg.from, msg.to));
+                }
+                return;
+            }
+        };
+
+        room.board
+            .make_move(chess_move, CastlingRights::default(), None, 0);
+        room.side_to_move = room.side_to_move.opponent();
+        room.moves_played += 1;
+
+        // Broadcast the move itself, not just the board it led to: a receiver resolves it
+        // against its own locally-tracked game the same way the `Lobby` just did, which keeps
+        // its castling/en-passant state intact in a way replaying only `fen` couldn't.
+        let move_msg = ChessMessage::Move {
+            from: msg.from,
+            to: msg.to,
+            promotion: msg.promotion,
+        };
+        let payload =
+            serde_json::to_string(&move_msg).expect("ChessMessage::Move always serializes");
+        room.broadcast(&payload);
+        room.relay_board();
+    }
+}
+
+/// Resolves a `MakeMove` against `board.legal_moves`, the move-generation subsystem `ChessBoard`
+/// carries on its own, so the server can never apply a move the board wouldn't generate itself.
+/// `promotion` disambiguates the four pieces a pawn reaching the back rank can become; a
+/// promotion move without one (or a non-promotion move with one) just fails to match anything.
+fn resolve_move(board: &ChessBoard, side_to_move: Color, msg: &MakeMove) -> Option<ChessMove> {
+    board
+        .legal_moves(msg.from, side_to_move)
+        .into_iter()
+        .find(|m| match m {
+            ChessMove::Regular(rm) => rm.to_idx() == msg.to && msg.promotion.is_none(),
+            ChessMove::Promotion(pm) => {
+                pm.to_idx() == msg.to && msg.promotion == Some(pm.promotion_piece())
+            }
+            ChessMove::Castle(cm) => cm.king_to() == msg.to && msg.promotion.is_none(),
+            ChessMove::EnPassant(epm) => epm.to_idx() == msg.to && msg.promotion.is_none(),
+        })
+}
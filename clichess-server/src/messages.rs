@@ -0,0 +1,107 @@
+use actix::{Message, Recipient};
+use chess::{ChessIndex, PieceType};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub use chess::{ChessMessage, Color};
+
+/// A raw text frame handed back to a `WsConn`, which writes it straight onto its websocket.
+/// The `Lobby` uses this to relay move/board JSON and plain-text status lines to seats and
+/// spectators; join failures are written by `WsConn` itself from `Connect`'s result.
+#[derive(Debug, Clone)]
+pub struct WsMessage(pub String);
+
+impl Message for WsMessage {
+    type Result = ();
+}
+
+/// Sent by a `WsConn` as soon as its actor starts, asking the `Lobby` to seat `self_id` in
+/// `room_id`. `create_if_missing` mirrors the `?create=` query flag on the connect route: a
+/// plain join stays strict (`JoinRoomError::DoesntExist` if nobody has opened the room yet),
+/// while the first player to open a room passes `true`. `token` distinguishes a fresh join
+/// (`None`, the `Lobby` mints a new one) from a reconnect attempt presenting the token handed
+/// out by an earlier `Connect` for the same seat.
+#[derive(Debug, Clone)]
+pub struct Connect {
+    pub addr: Recipient<WsMessage>,
+    pub self_id: Uuid,
+    pub room_id: String,
+    pub create_if_missing: bool,
+    pub token: Option<Uuid>,
+}
+
+/// A successful `Connect`: which side this connection plays, and the persistent token to
+/// present on a future reconnect.
+#[derive(Debug, Clone, Copy)]
+pub struct Seated {
+    pub color: Color,
+    pub token: Uuid,
+}
+
+/// How a `Connect` was resolved: a third player (and beyond) joining an already-full room
+/// doesn't get rejected, it's let in read-only as a spectator instead.
+#[derive(Debug, Clone, Copy)]
+pub enum JoinOutcome {
+    /// This connection took one of the room's two seats, playing `Seated::color`.
+    Seated(Seated),
+    /// The room already had two seated players, so this connection was added as a read-only
+    /// spectator instead.
+    Spectating,
+}
+
+impl Message for Connect {
+    type Result = Result<JoinOutcome, JoinRoomError>;
+}
+
+/// Sent by `WsConn::stopping`, so the `Lobby` can mark the seat vacant and let the opponent
+/// know without tearing down the room itself.
+#[derive(Debug, Clone)]
+pub struct Disconnect {
+    pub id: Uuid,
+    pub room_id: String,
+}
+
+impl Message for Disconnect {
+    type Result = ();
+}
+
+/// A `ChessMessage::Move` `WsConn` has already deserialized off the wire, tagged with who sent
+/// it and which room it applies to — the typed replacement for forwarding the raw frame on as
+/// opaque text.
+#[derive(Debug, Clone)]
+pub struct MakeMove {
+    pub id: Uuid,
+    pub room_id: String,
+    pub from: ChessIndex,
+    pub to: ChessIndex,
+    pub promotion: Option<PieceType>,
+}
+
+impl Message for MakeMove {
+    type Result = ();
+}
+
+/// Why a `Connect` was rejected before a seat (or a spectating slot) could be handed out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JoinRoomError {
+    /// `room_id` was never opened by a first player (`create_if_missing` was false).
+    DoesntExist,
+    /// The room's game already has moves played; joining mid-game isn't supported, only the
+    /// two original players may keep playing it out. Doesn't apply to spectators, who are
+    /// welcome to start watching at any point.
+    AlreadyStarted,
+    /// A reconnect presented a token that doesn't match a seat in this room, either because it
+    /// was never issued here or because its grace period already expired and the room is gone.
+    UnknownToken,
+}
+
+impl std::fmt::Display for JoinRoomError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let output = match self {
+            JoinRoomError::DoesntExist => "room does not exist",
+            JoinRoomError::AlreadyStarted => "room's game has already started",
+            JoinRoomError::UnknownToken => "reconnect token not recognized",
+        };
+        write!(f, "{}", output)
+    }
+}
@@ -1,17 +1,40 @@
 use crate::lobby::Lobby;
 use crate::ws::WsConn;
 use actix::Addr;
-use actix_web::{get, web::Data, web::Path, web::Payload, Error, HttpRequest, HttpResponse};
+use actix_web::{
+    get, web::Data, web::Path, web::Payload, web::Query, Error, HttpRequest, HttpResponse,
+};
 use actix_web_actors::ws;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct JoinQuery {
+    /// `?create=true` opens `room_id` if nobody has joined it yet; omitted (or `false`), joining
+    /// a room nobody opened fails with `JoinRoomError::DoesntExist` instead of silently creating
+    /// one out from under a typo'd room name.
+    #[serde(default)]
+    create: bool,
+    /// `?token=<uuid>` presents the reconnect token handed out by an earlier `Connect` for this
+    /// seat, so a dropped connection can rebind to its room instead of taking a fresh seat.
+    /// Omitted for a first-time join.
+    token: Option<Uuid>,
+}
 
 #[get("/{room_id}")]
 pub async fn start_connection(
     req: HttpRequest,
     stream: Payload,
     Path(room_id): Path<String>,
+    Query(join_query): Query<JoinQuery>,
     srv: Data<Addr<Lobby>>,
 ) -> Result<HttpResponse, Error> {
-    let ws = WsConn::new(room_id, srv.get_ref().clone());
+    let ws = WsConn::new(
+        room_id,
+        join_query.create,
+        join_query.token,
+        srv.get_ref().clone(),
+    );
 
     let resp = ws::start(ws, &req, stream)?;
     Ok(resp)
@@ -1,5 +1,5 @@
 use crate::lobby::Lobby;
-use crate::messages::{ClientActorMessage, Connect, Disconnect, WsMessage};
+use crate::messages::{ChessMessage, Color, Connect, Disconnect, JoinOutcome, MakeMove, WsMessage};
 use actix::{fut, ActorContext, ActorFuture, ContextFutureSpawner, WrapFuture};
 use actix::{Actor, Addr, Running, StreamHandler};
 use actix::{AsyncContext, Handler};
@@ -12,18 +12,32 @@ const CLIENT_TIMEOUT: Duration = Duration::from_secs(10);
 
 pub struct WsConn {
     room: String,
+    create_if_missing: bool,
+    /// The reconnect token presented on the `?token=` query flag, if any, asking the `Lobby` to
+    /// rebind this connection to a seat it already holds instead of handing out a fresh one.
+    token: Option<Uuid>,
     lobby_addr: Addr<Lobby>,
     hb: Instant,
     id: Uuid,
+    /// Which side the lobby seated this connection as, once `Connect` has been acknowledged.
+    color: Option<Color>,
 }
 
 impl WsConn {
-    pub fn new(room: String, lobby: Addr<Lobby>) -> WsConn {
+    pub fn new(
+        room: String,
+        create_if_missing: bool,
+        token: Option<Uuid>,
+        lobby: Addr<Lobby>,
+    ) -> WsConn {
         WsConn {
             id: Uuid::new_v4(),
             room,
+            create_if_missing,
+            token,
             hb: Instant::now(),
             lobby_addr: lobby,
+            color: None,
         }
     }
 
@@ -56,12 +70,25 @@ impl Actor for WsConn {
                 addr: addr.recipient(),
                 room_id: self.room.clone(),
                 self_id: self.id,
+                create_if_missing: self.create_if_missing,
+                token: self.token,
             })
             .into_actor(self)
-            .then(|res, _, ctx| {
+            .then(|res, act, ctx| {
                 match res {
-                    Ok(_res) => (),
-                    _ => ctx.stop(),
+                    Ok(Ok(JoinOutcome::Seated(seated))) => {
+                        act.color = Some(seated.color);
+                        act.token = Some(seated.token);
+                        ctx.text(format!("seated {} token {}", seated.color, seated.token));
+                    }
+                    Ok(Ok(JoinOutcome::Spectating)) => {
+                        ctx.text("spectating");
+                    }
+                    Ok(Err(join_err)) => {
+                        ctx.text(format!("couldn't join room: {}", join_err));
+                        ctx.stop();
+                    }
+                    Err(_mailbox_err) => ctx.stop(),
                 }
                 fut::ready(())
             })
@@ -96,11 +123,23 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for WsConn {
                 ctx.stop();
             }
             Ok(ws::Message::Nop) => (),
-            Ok(ws::Message::Text(s)) => self.lobby_addr.do_send(ClientActorMessage {
-                id: self.id,
-                content: s,
-                room_id: self.room.clone(),
-            }),
+            Ok(ws::Message::Text(s)) => match serde_json::from_str::<ChessMessage>(&s) {
+                Ok(ChessMessage::Move {
+                    from,
+                    to,
+                    promotion,
+                }) => self.lobby_addr.do_send(MakeMove {
+                    id: self.id,
+                    room_id: self.room.clone(),
+                    from,
+                    to,
+                    promotion,
+                }),
+                Ok(ChessMessage::BoardState { .. }) => {
+                    ctx.text("clients don't send board state, only moves")
+                }
+                Err(e) => ctx.text(format!("couldn't parse move: {}", e)),
+            },
             Err(e) => panic!(e),
         }
     }
@@ -0,0 +1,8 @@
+use crate::{ChessMove, Game};
+
+/// A pluggable move-choosing policy for `ComputerPlayer<T>`: anything that can look at a
+/// position and hand back the move it wants to play, whether that's an in-process search or a
+/// handshake with an external engine (see `uci::UciStrategy`).
+pub trait Strategy {
+    fn get_move(&self, game: &Game) -> ChessMove;
+}
@@ -0,0 +1,253 @@
+use crate::{ChessIndex, File, FileIter, Rank, RankIter};
+use std::ops::{BitAnd, BitOr, BitOrAssign, BitXor, Not};
+
+/// A 64-bit set of squares, one bit per `ChessIndex`, using the same `linear_value()` ordering
+/// as `ChessBoard` (`a1` = bit 0, `h8` = bit 63).
+///
+/// This backs the attack-generation helpers used by `ChessBoard::attacks`. We don't (yet) go as
+/// far as precomputed magic-bitboard multipliers generated by a `build.rs` — instead sliding
+/// attacks are computed classically: walk each ray from the masks below until a blocker (or the
+/// edge of the board) is hit. This is slower than a magic lookup but needs no offline table
+/// generation, and it's a drop-in internal swap if that's ever worth the complexity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Bitboard(u64);
+
+impl Bitboard {
+    pub const EMPTY: Bitboard = Bitboard(0);
+
+    pub fn from_index(index: ChessIndex) -> Self {
+        Bitboard(1u64 << index.linear_value())
+    }
+
+    pub fn is_set(&self, index: ChessIndex) -> bool {
+        self.0 & (1u64 << index.linear_value()) != 0
+    }
+
+    pub fn set(&mut self, index: ChessIndex) {
+        self.0 |= 1u64 << index.linear_value();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn bits(&self) -> u64 {
+        self.0
+    }
+
+    /// The set squares, lowest bit (`a1`) first. Lets move generators turn an attack bitboard
+    /// straight into a sequence of destination squares instead of re-scanning the board.
+    pub fn squares(&self) -> impl Iterator<Item = ChessIndex> + '_ {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                None
+            } else {
+                let square = bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                Some(ChessIndex::from_linear_value(square))
+            }
+        })
+    }
+}
+
+impl BitOr for Bitboard {
+    type Output = Bitboard;
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Bitboard(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Bitboard {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for Bitboard {
+    type Output = Bitboard;
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Bitboard(self.0 & rhs.0)
+    }
+}
+
+impl BitXor for Bitboard {
+    type Output = Bitboard;
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        Bitboard(self.0 ^ rhs.0)
+    }
+}
+
+impl Not for Bitboard {
+    type Output = Bitboard;
+    fn not(self) -> Self::Output {
+        Bitboard(!self.0)
+    }
+}
+
+/// Knight attack set from `index`, ignoring occupancy (knights jump over pieces).
+pub fn knight_attacks(index: ChessIndex) -> Bitboard {
+    leaper_attacks(index, &KNIGHT_OFFSETS)
+}
+
+/// King attack set from `index` (the eight neighbouring squares).
+pub fn king_attacks(index: ChessIndex) -> Bitboard {
+    leaper_attacks(index, &KING_OFFSETS)
+}
+
+/// Rook attacks from `index` given the board's combined occupancy, stopping at (and including)
+/// the first blocker in each of the four ray directions.
+pub fn rook_attacks(index: ChessIndex, occupancy: Bitboard) -> Bitboard {
+    let mut attacks = Bitboard::EMPTY;
+    for &(file_step, rank_step) in &ROOK_DIRECTIONS {
+        attacks |= ray_attacks(index, file_step, rank_step, occupancy);
+    }
+    attacks
+}
+
+/// Bishop attacks from `index` given the board's combined occupancy.
+pub fn bishop_attacks(index: ChessIndex, occupancy: Bitboard) -> Bitboard {
+    let mut attacks = Bitboard::EMPTY;
+    for &(file_step, rank_step) in &BISHOP_DIRECTIONS {
+        attacks |= ray_attacks(index, file_step, rank_step, occupancy);
+    }
+    attacks
+}
+
+/// Queen attacks: the union of rook and bishop attacks from `index`.
+pub fn queen_attacks(index: ChessIndex, occupancy: Bitboard) -> Bitboard {
+    rook_attacks(index, occupancy) | bishop_attacks(index, occupancy)
+}
+
+/// The two diagonal squares a `color` pawn on `index` attacks (not its forward push, which
+/// isn't a capture). Ignores whether those squares are actually occupied by an enemy piece —
+/// like the other `*_attacks` helpers, that's for the caller to check against occupancy.
+pub fn pawn_attacks(index: ChessIndex, color: crate::Color) -> Bitboard {
+    let rank_step = match color {
+        crate::Color::White => 1,
+        crate::Color::Black => -1,
+    };
+    leaper_attacks(index, &[(1, rank_step), (-1, rank_step)])
+}
+
+const KNIGHT_OFFSETS: [(i32, i32); 8] = [
+    (2, 1),
+    (2, -1),
+    (-2, 1),
+    (-2, -1),
+    (1, 2),
+    (1, -2),
+    (-1, 2),
+    (-1, -2),
+];
+
+const KING_OFFSETS: [(i32, i32); 8] = [
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+const ROOK_DIRECTIONS: [(i32, i32); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+const BISHOP_DIRECTIONS: [(i32, i32); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+fn leaper_attacks(index: ChessIndex, offsets: &[(i32, i32)]) -> Bitboard {
+    let mut attacks = Bitboard::EMPTY;
+    for &(file_offset, rank_offset) in offsets {
+        if let Some(to) = index.offset_checked(file_offset, rank_offset) {
+            attacks.set(to);
+        }
+    }
+    attacks
+}
+
+/// Attacks along a single `(file_step, rank_step)` ray from `index`, stopping at (and including)
+/// the first blocker. `rook_attacks`/`bishop_attacks` union four of these; `Game`'s per-direction
+/// sliding-move helper (`moves_to_opponents_piece`) calls this directly for one ray at a time.
+pub(crate) fn ray_attacks(
+    index: ChessIndex,
+    file_step: i32,
+    rank_step: i32,
+    occupancy: Bitboard,
+) -> Bitboard {
+    let mut attacks = Bitboard::EMPTY;
+    let mut current = index;
+    while let Some(next) = current.offset_checked(file_step, rank_step) {
+        attacks.set(next);
+        if occupancy.is_set(next) {
+            break;
+        }
+        current = next;
+    }
+    attacks
+}
+
+impl ChessIndex {
+    /// Like `file() + file_delta` / `rank() + rank_delta` combined, returning `None` if either
+    /// axis would leave the board.
+    pub(crate) fn offset_checked(&self, file_delta: i32, rank_delta: i32) -> Option<ChessIndex> {
+        use std::convert::TryFrom;
+        let file = i32::from(u8::from(&self.file())) + file_delta;
+        let rank = i32::from(u8::from(&self.rank())) + rank_delta;
+        ChessIndex::try_from((file, rank)).ok()
+    }
+}
+
+pub fn file_mask(file: File) -> Bitboard {
+    let mut bb = Bitboard::EMPTY;
+    for rank in RankIter::start_at(Rank::First) {
+        bb.set(ChessIndex::new(file, rank));
+    }
+    bb
+}
+
+pub fn rank_mask(rank: Rank) -> Bitboard {
+    let mut bb = Bitboard::EMPTY;
+    for file in FileIter::start_at(File::A) {
+        bb.set(ChessIndex::new(file, rank));
+    }
+    bb
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::*;
+
+    #[test]
+    fn test_knight_attacks() {
+        let attacks = knight_attacks(E4);
+        assert!(attacks.is_set(F6));
+        assert!(attacks.is_set(D6));
+        assert!(attacks.is_set(C5));
+        assert!(!attacks.is_set(E5));
+    }
+
+    #[test]
+    fn test_rook_attacks_stop_at_blocker() {
+        let mut occupancy = Bitboard::EMPTY;
+        occupancy.set(E6);
+
+        let attacks = rook_attacks(E4, occupancy);
+        assert!(attacks.is_set(E5));
+        assert!(attacks.is_set(E6));
+        assert!(!attacks.is_set(E7));
+    }
+
+    #[test]
+    fn test_file_and_rank_mask() {
+        let a_file = file_mask(File::A);
+        assert!(a_file.is_set(A1));
+        assert!(a_file.is_set(A8));
+        assert!(!a_file.is_set(B1));
+
+        let first_rank = rank_mask(Rank::First);
+        assert!(first_rank.is_set(A1));
+        assert!(first_rank.is_set(H1));
+        assert!(!first_rank.is_set(A2));
+    }
+}
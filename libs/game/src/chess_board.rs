@@ -1,7 +1,13 @@
-use crate::{file::FileIter, rank::RankIter, square::Square, ChessIndex, Color, File, Piece, Rank};
+use crate::{
+    consts::*, file::FileIter, rank::RankIter, square::Square, CastlingRights, ChessIndex,
+    ChessMove, Color, File, InvalidError, Piece, PieceType, Rank,
+};
 use std::{
+    convert::TryFrom,
+    error::Error,
     fmt::Display,
     ops::{Index, IndexMut},
+    str::FromStr,
 };
 
 #[derive(Debug, Clone)]
@@ -10,6 +16,53 @@ pub struct ChessBoard {
 }
 
 impl ChessBoard {
+    /// The standard chess starting position: both back ranks and pawn ranks filled in, every
+    /// other square empty. `ChessBoard::default()` stays an empty board so callers building up
+    /// a position square by square (e.g. FEN parsing) aren't fighting pre-placed pieces.
+    pub fn starting_position() -> Self {
+        use Color::*;
+
+        let mut board = Self::default();
+
+        board.set_piece(A1, Piece::rook(White));
+        board.set_piece(B1, Piece::knight(White));
+        board.set_piece(C1, Piece::bishop(White));
+        board.set_piece(D1, Piece::queen(White));
+        board.set_piece(E1, Piece::king(White));
+        board.set_piece(F1, Piece::bishop(White));
+        board.set_piece(G1, Piece::knight(White));
+        board.set_piece(H1, Piece::rook(White));
+
+        board.set_piece(A2, Piece::pawn(White));
+        board.set_piece(B2, Piece::pawn(White));
+        board.set_piece(C2, Piece::pawn(White));
+        board.set_piece(D2, Piece::pawn(White));
+        board.set_piece(E2, Piece::pawn(White));
+        board.set_piece(F2, Piece::pawn(White));
+        board.set_piece(G2, Piece::pawn(White));
+        board.set_piece(H2, Piece::pawn(White));
+
+        board.set_piece(A7, Piece::pawn(Black));
+        board.set_piece(B7, Piece::pawn(Black));
+        board.set_piece(C7, Piece::pawn(Black));
+        board.set_piece(D7, Piece::pawn(Black));
+        board.set_piece(E7, Piece::pawn(Black));
+        board.set_piece(F7, Piece::pawn(Black));
+        board.set_piece(G7, Piece::pawn(Black));
+        board.set_piece(H7, Piece::pawn(Black));
+
+        board.set_piece(A8, Piece::rook(Black));
+        board.set_piece(B8, Piece::knight(Black));
+        board.set_piece(C8, Piece::bishop(Black));
+        board.set_piece(D8, Piece::queen(Black));
+        board.set_piece(E8, Piece::king(Black));
+        board.set_piece(F8, Piece::bishop(Black));
+        board.set_piece(G8, Piece::knight(Black));
+        board.set_piece(H8, Piece::rook(Black));
+
+        board
+    }
+
     pub fn piece_at(&self, idx: ChessIndex) -> Option<&Piece> {
         self[idx].piece()
     }
@@ -28,8 +81,815 @@ impl ChessBoard {
     pub fn take_piece(&mut self, idx: ChessIndex) -> Option<Piece> {
         self[idx].take_piece()
     }
+
+    /// All squares occupied by any piece, as a `Bitboard`.
+    pub fn occupancy(&self) -> crate::Bitboard {
+        let mut occupancy = crate::Bitboard::EMPTY;
+        for rank in RankIter::start_at(Rank::First) {
+            for file in FileIter::start_at(File::A) {
+                let idx = ChessIndex::new(file, rank);
+                if self.piece_at(idx).is_some() {
+                    occupancy.set(idx);
+                }
+            }
+        }
+        occupancy
+    }
+
+    /// All squares occupied by a piece belonging to `color`, as a `Bitboard`.
+    pub fn occupancy_for(&self, color: Color) -> crate::Bitboard {
+        let mut occupancy = crate::Bitboard::EMPTY;
+        for rank in RankIter::start_at(Rank::First) {
+            for file in FileIter::start_at(File::A) {
+                let idx = ChessIndex::new(file, rank);
+                if matches!(self.piece_at(idx), Some(p) if p.color() == color) {
+                    occupancy.set(idx);
+                }
+            }
+        }
+        occupancy
+    }
+
+    /// The squares attacked by whatever piece (if any) stands on `idx`, ignoring whose turn it
+    /// is and pin/check legality — this is the raw attack set a bitboard-backed move generator
+    /// builds on top of.
+    pub fn attacks(&self, idx: ChessIndex) -> crate::Bitboard {
+        let piece = match self.piece_at(idx) {
+            Some(p) => p,
+            None => return crate::Bitboard::EMPTY,
+        };
+
+        let occupancy = self.occupancy();
+        match piece.piece_type() {
+            PieceType::Knight => crate::knight_attacks(idx),
+            PieceType::King => crate::king_attacks(idx),
+            PieceType::Rook => crate::rook_attacks(idx, occupancy),
+            PieceType::Bishop => crate::bishop_attacks(idx, occupancy),
+            PieceType::Queen => crate::queen_attacks(idx, occupancy),
+            PieceType::Pawn => crate::pawn_attacks(idx, piece.color()),
+        }
+    }
+
+    /// Whether any `by`-colored piece on the board attacks `idx`, built on top of `attacks` for
+    /// every occupied square of that color. Doesn't know about pins or whose turn it is — just
+    /// raw board geometry, the same caveat `attacks` carries.
+    pub fn is_attacked(&self, idx: ChessIndex, by: Color) -> bool {
+        for rank in RankIter::start_at(Rank::First) {
+            for file in FileIter::start_at(File::A) {
+                let from = ChessIndex::new(file, rank);
+                if matches!(self.piece_at(from), Some(p) if p.color() == by)
+                    && self.attacks(from).is_set(idx)
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// The square occupied by `color`'s king, or `None` if it has somehow been captured (e.g. a
+    /// scratch position assembled by hand rather than reached by legal play).
+    fn king_square(&self, color: Color) -> Option<ChessIndex> {
+        for rank in RankIter::start_at(Rank::First) {
+            for file in FileIter::start_at(File::A) {
+                let idx = ChessIndex::new(file, rank);
+                if matches!(self.piece_at(idx), Some(p) if p.is_king() && p.color() == color) {
+                    return Some(idx);
+                }
+            }
+        }
+        None
+    }
+
+    /// Whether `color`'s king currently stands on a square attacked by the opponent — built
+    /// directly on `is_attacked`, the "scanning opponent pieces" this is documented to do.
+    pub fn is_in_check(&self, color: Color) -> bool {
+        match self.king_square(color) {
+            Some(king) => self.is_attacked(king, color.opponent()),
+            None => false,
+        }
+    }
+
+    /// Every legal move for the `side_to_move` piece on `idx` — every pseudo-legal candidate
+    /// `pseudo_legal_moves_from` generates for it, minus the ones that would leave `side_to_move`'s
+    /// own king in check. This is `ChessBoard`'s own, self-contained move generator: unlike
+    /// `Game`'s (which tracks castling rights and the en-passant target as explicit state), it
+    /// reads both back out of `Piece::history`, so it works from just the board alone.
+    pub fn legal_moves(&self, idx: ChessIndex, side_to_move: Color) -> Vec<ChessMove> {
+        self.pseudo_legal_moves_from(idx, side_to_move)
+            .into_iter()
+            .filter(|&chess_move| !self.leaves_king_in_check(chess_move, side_to_move))
+            .collect()
+    }
+
+    /// Every legal move for every `color` piece on the board, in board order.
+    pub fn moves_for(&self, color: Color) -> Vec<ChessMove> {
+        let mut moves = Vec::new();
+        for rank in RankIter::start_at(Rank::First) {
+            for file in FileIter::start_at(File::A) {
+                let idx = ChessIndex::new(file, rank);
+                if matches!(self.piece_at(idx), Some(p) if p.color() == color) {
+                    moves.append(&mut self.legal_moves(idx, color));
+                }
+            }
+        }
+        moves
+    }
+
+    /// Plays `chess_move` out on a scratch clone and checks whether it leaves `side_to_move`'s
+    /// king in check — `legal_moves`'s king-safety filter. Castling rights/en-passant/halfmove
+    /// clock don't matter for this check, so `make_move` gets harmless placeholders; nothing
+    /// reads them back out of the clone.
+    fn leaves_king_in_check(&self, chess_move: ChessMove, side_to_move: Color) -> bool {
+        let mut board = self.clone();
+        board.make_move(chess_move, CastlingRights::default(), None, 0);
+        board.is_in_check(side_to_move)
+    }
+
+    /// Pseudo-legal candidates for the `side_to_move` piece on `idx` — obeys piece geometry and
+    /// occupancy but not yet king safety (see `legal_moves` for that filter). Sliding/leaping
+    /// pieces just turn their `attacks` bitboard into moves; pawns and the king get their own
+    /// handling since pushes aren't attacks and castling is a special case.
+    fn pseudo_legal_moves_from(&self, idx: ChessIndex, side_to_move: Color) -> Vec<ChessMove> {
+        let piece = match self.piece_at(idx) {
+            Some(p) if p.color() == side_to_move => p,
+            _ => return Vec::new(),
+        };
+
+        match piece.piece_type() {
+            PieceType::Pawn => self.pseudo_legal_pawn_moves(idx, side_to_move),
+            PieceType::King => self.pseudo_legal_king_moves(idx, side_to_move),
+            _ => (self.attacks(idx) & !self.occupancy_for(side_to_move))
+                .squares()
+                .map(|to| ChessMove::regular(idx, to))
+                .collect(),
+        }
+    }
+
+    fn pseudo_legal_king_moves(&self, idx: ChessIndex, side_to_move: Color) -> Vec<ChessMove> {
+        let mut moves: Vec<ChessMove> =
+            (crate::king_attacks(idx) & !self.occupancy_for(side_to_move))
+                .squares()
+                .map(|to| ChessMove::regular(idx, to))
+                .collect();
+
+        moves.extend(self.castling_moves(idx, side_to_move));
+        moves
+    }
+
+    /// Castling candidates for the king on `king_idx`, eligible only if the king hasn't moved
+    /// (per `Piece::has_made_move`) and isn't currently in check; each side is then checked
+    /// independently against its own rook.
+    fn castling_moves(&self, king_idx: ChessIndex, side_to_move: Color) -> Vec<ChessMove> {
+        match self.piece_at(king_idx) {
+            Some(p) if p.is_king() && !p.has_made_move() => {}
+            _ => return Vec::new(),
+        }
+        if self.is_attacked(king_idx, side_to_move.opponent()) {
+            return Vec::new();
+        }
+
+        let rank = king_idx.rank();
+        [File::H, File::A]
+            .iter()
+            .copied()
+            .filter_map(|rook_file| {
+                self.castling_move(king_idx, ChessIndex::new(rook_file, rank), side_to_move)
+            })
+            .collect()
+    }
+
+    /// A single castling candidate toward the rook on `rook_idx`, or `None` if that rook hasn't
+    /// stayed put (`Piece::has_made_move`), the squares between king and rook aren't all empty,
+    /// or the king would pass through or land on an attacked square.
+    fn castling_move(
+        &self,
+        king_idx: ChessIndex,
+        rook_idx: ChessIndex,
+        side_to_move: Color,
+    ) -> Option<ChessMove> {
+        match self.piece_at(rook_idx) {
+            Some(p) if p.is_rook() && p.color() == side_to_move && !p.has_made_move() => {}
+            _ => return None,
+        }
+
+        let kingside = rook_idx.file() > king_idx.file();
+        let step: i8 = if kingside { 1 } else { -1 };
+        let king_to = king_idx.offset(2 * step, 0)?;
+        let rook_to = king_idx.offset(step, 0)?;
+
+        let between = ChessIndex::indices_between(king_idx, rook_idx);
+        if between[1..between.len() - 1]
+            .iter()
+            .any(|&idx| self.piece_at(idx).is_some())
+        {
+            return None;
+        }
+
+        if ChessIndex::indices_between(king_idx, king_to)
+            .into_iter()
+            .skip(1)
+            .any(|idx| self.is_attacked(idx, side_to_move.opponent()))
+        {
+            return None;
+        }
+
+        Some(ChessMove::castle(king_idx, king_to, rook_idx, rook_to))
+    }
+
+    fn pseudo_legal_pawn_moves(&self, idx: ChessIndex, side_to_move: Color) -> Vec<ChessMove> {
+        let forward: i8 = match side_to_move {
+            Color::White => 1,
+            Color::Black => -1,
+        };
+        let start_rank = match side_to_move {
+            Color::White => Rank::Second,
+            Color::Black => Rank::Seventh,
+        };
+        let promotion_rank = match side_to_move {
+            Color::White => Rank::Eighth,
+            Color::Black => Rank::First,
+        };
+
+        let mut moves = Vec::new();
+
+        if let Some(one_step) = idx.offset(0, forward) {
+            if self.piece_at(one_step).is_none() {
+                if one_step.rank() == promotion_rank {
+                    moves.extend(ChessMove::promotions(idx, one_step));
+                } else {
+                    moves.push(ChessMove::regular(idx, one_step));
+                }
+
+                if idx.rank() == start_rank {
+                    if let Some(two_step) = idx.offset(0, 2 * forward) {
+                        if self.piece_at(two_step).is_none() {
+                            moves.push(ChessMove::regular(idx, two_step));
+                        }
+                    }
+                }
+            }
+        }
+
+        let opponent_occupancy = self.occupancy_for(side_to_move.opponent());
+        for to in (crate::pawn_attacks(idx, side_to_move) & opponent_occupancy).squares() {
+            if to.rank() == promotion_rank {
+                moves.extend(ChessMove::promotions(idx, to));
+            } else {
+                moves.push(ChessMove::regular(idx, to));
+            }
+        }
+
+        moves.extend(self.en_passant_moves(idx, side_to_move));
+
+        moves
+    }
+
+    /// En-passant candidates for the pawn on `idx`, detected from `Piece::history` instead of
+    /// tracked state: an adjacent opponent pawn whose most recent move was a two-square advance
+    /// landing level with `idx` is a legal en-passant target.
+    fn en_passant_moves(&self, idx: ChessIndex, side_to_move: Color) -> Vec<ChessMove> {
+        let forward: i8 = match side_to_move {
+            Color::White => 1,
+            Color::Black => -1,
+        };
+
+        [-1i8, 1i8]
+            .iter()
+            .copied()
+            .filter_map(|file_delta| {
+                let neighbour_idx = idx.offset(file_delta, 0)?;
+                let neighbour = self.piece_at(neighbour_idx)?;
+                if !neighbour.is_pawn() || neighbour.color() == side_to_move {
+                    return None;
+                }
+                let came_from = neighbour.previous_index()?;
+                if came_from.rank() != (idx.rank() + (forward as i32) * 2)? {
+                    return None;
+                }
+                let to = idx.offset(file_delta, forward)?;
+                Some(ChessMove::en_passant(idx, to, neighbour_idx))
+            })
+            .collect()
+    }
+
+    /// Plays `chess_move` on this board in place and returns the `UndoInfo` needed to reverse it
+    /// with `unmake_move` — the reversible-pair counterpart to `set_piece`/`take_piece` that lets
+    /// a search or perft walk the move tree without cloning a `ChessBoard` per node.
+    ///
+    /// `ChessBoard` doesn't track castling rights, the en-passant target, or the halfmove clock
+    /// itself (that's `Game`'s job), so `castling_rights`, `en_passant_target`, and
+    /// `halfmove_clock` are simply the caller's current values going into the move; they're
+    /// carried through unchanged in the returned `UndoInfo` for `unmake_move` to hand back
+    /// untouched.
+    pub fn make_move(
+        &mut self,
+        chess_move: ChessMove,
+        castling_rights: CastlingRights,
+        en_passant_target: Option<ChessIndex>,
+        halfmove_clock: u32,
+    ) -> UndoInfo {
+        let captured = match chess_move {
+            ChessMove::Regular(m) => self.piece_at(m.to_idx()).map(Piece::piece_type),
+            ChessMove::Promotion(m) => self.piece_at(m.to_idx()).map(Piece::piece_type),
+            ChessMove::EnPassant(m) => self.piece_at(m.taken_pawn_idx()).map(Piece::piece_type),
+            ChessMove::Castle(_) => None,
+        };
+
+        match chess_move {
+            ChessMove::Regular(m) => {
+                let piece = self
+                    .take_piece(m.from_idx())
+                    .expect("no piece on regular move's from square");
+                self.set_piece(m.to_idx(), piece);
+            }
+            ChessMove::Castle(m) => {
+                let king = self
+                    .take_piece(m.king_from())
+                    .expect("no king on castle move's king_from square");
+                self.set_piece(m.king_to(), king);
+                let rook = self
+                    .take_piece(m.rook_from())
+                    .expect("no rook on castle move's rook_from square");
+                self.set_piece(m.rook_to(), rook);
+            }
+            ChessMove::Promotion(m) => {
+                let pawn = self
+                    .take_piece(m.from_idx())
+                    .expect("no pawn on promotion move's from square");
+                self.set_piece(m.to_idx(), Piece::new(m.promotion_piece(), pawn.color()));
+            }
+            ChessMove::EnPassant(m) => {
+                self.take_piece(m.taken_pawn_idx());
+                let pawn = self
+                    .take_piece(m.from_idx())
+                    .expect("no pawn on en passant move's from square");
+                self.set_piece(m.to_idx(), pawn);
+            }
+        }
+
+        UndoInfo {
+            captured,
+            prior_castling_rights: castling_rights,
+            prior_en_passant_target: en_passant_target,
+            prior_halfmove_clock: halfmove_clock,
+        }
+    }
+
+    /// Reverses a `make_move` call: given the same `chess_move` and the `UndoInfo` it returned,
+    /// restores every square `make_move` touched (including putting a captured piece back) and
+    /// hands back the caller's castling rights, en-passant target, and halfmove clock from
+    /// before the move, so the caller can restore its own state in turn.
+    pub fn unmake_move(
+        &mut self,
+        chess_move: ChessMove,
+        undo: UndoInfo,
+    ) -> (CastlingRights, Option<ChessIndex>, u32) {
+        match chess_move {
+            ChessMove::Regular(m) => {
+                let piece = self
+                    .take_piece(m.to_idx())
+                    .expect("no piece on undone move's to square");
+                if let Some(captured_type) = undo.captured {
+                    self.set_piece(m.to_idx(), Piece::new(captured_type, piece.color().opponent()));
+                }
+                self.set_piece(m.from_idx(), piece);
+            }
+            ChessMove::Castle(m) => {
+                let king = self
+                    .take_piece(m.king_to())
+                    .expect("no king on undone castle's to square");
+                self.set_piece(m.king_from(), king);
+                let rook = self
+                    .take_piece(m.rook_to())
+                    .expect("no rook on undone castle's to square");
+                self.set_piece(m.rook_from(), rook);
+            }
+            ChessMove::Promotion(m) => {
+                let promoted = self
+                    .take_piece(m.to_idx())
+                    .expect("no promoted piece on undone move's to square");
+                if let Some(captured_type) = undo.captured {
+                    self.set_piece(
+                        m.to_idx(),
+                        Piece::new(captured_type, promoted.color().opponent()),
+                    );
+                }
+                self.set_piece(m.from_idx(), Piece::new(PieceType::Pawn, promoted.color()));
+            }
+            ChessMove::EnPassant(m) => {
+                let pawn = self
+                    .take_piece(m.to_idx())
+                    .expect("no pawn on undone en passant's to square");
+                let captured_type = undo
+                    .captured
+                    .expect("en passant undo record must carry the taken pawn's type");
+                self.set_piece(
+                    m.taken_pawn_idx(),
+                    Piece::new(captured_type, pawn.color().opponent()),
+                );
+                self.set_piece(m.from_idx(), pawn);
+            }
+        }
+
+        (
+            undo.prior_castling_rights,
+            undo.prior_en_passant_target,
+            undo.prior_halfmove_clock,
+        )
+    }
+
+    /// The Zobrist hash of just this board's piece placement, XORing in `zobrist::piece_key`
+    /// for every occupied square. `Game::zobrist_hash` folds in side-to-move, castling rights,
+    /// and the en-passant target on top of this, since `ChessBoard` doesn't carry that state.
+    pub fn zobrist_hash(&self) -> u64 {
+        let mut hash = 0u64;
+
+        for rank in RankIter::start_at(Rank::First) {
+            for file in FileIter::start_at(File::A) {
+                let idx = ChessIndex::new(file, rank);
+                if let Some(piece) = self.piece_at(idx) {
+                    hash ^= crate::zobrist::piece_key(piece.piece_type(), piece.color(), idx);
+                }
+            }
+        }
+
+        hash
+    }
+
+    /// Scores this position in centipawns from `side`'s perspective (positive means `side` is
+    /// better), using `PieceSquareTables::default()` for the positional half of the score. See
+    /// `evaluate_with` for scoring against a caller-tuned set of tables.
+    pub fn evaluate(&self, side: Color) -> i32 {
+        self.evaluate_with(side, &PieceSquareTables::default())
+    }
+
+    /// Like `evaluate`, but the positional bonus for every occupied square comes from `tables`
+    /// instead of the built-in defaults. Material (see `material_value`) plus `tables`'s bonus
+    /// is summed for `side` and subtracted for the opponent, so the result flips sign depending
+    /// on which `side` is asking.
+    pub fn evaluate_with(&self, side: Color, tables: &PieceSquareTables) -> i32 {
+        let mut white_relative_score = 0;
+
+        for rank in RankIter::start_at(Rank::First) {
+            for file in FileIter::start_at(File::A) {
+                let idx = ChessIndex::new(file, rank);
+                if let Some(piece) = self.piece_at(idx) {
+                    let value =
+                        material_value(piece.piece_type()) + square_bonus(tables, piece, idx);
+                    white_relative_score += match piece.color() {
+                        Color::White => value,
+                        Color::Black => -value,
+                    };
+                }
+            }
+        }
+
+        match side {
+            Color::White => white_relative_score,
+            Color::Black => -white_relative_score,
+        }
+    }
+
+    /// Encodes just the piece-placement field of FEN (ranks 8 down to 1, `/`-separated,
+    /// consecutive empty squares collapsed into a digit).
+    pub fn to_fen(&self) -> String {
+        let mut ranks = Vec::new();
+
+        for rank in RankIter::start_at(Rank::Eighth).rev() {
+            let mut rank_str = String::new();
+            let mut empty_run = 0;
+            for file in FileIter::start_at(File::A) {
+                match self[ChessIndex::new(file, rank)].piece() {
+                    Some(piece) => {
+                        if empty_run > 0 {
+                            rank_str.push_str(&empty_run.to_string());
+                            empty_run = 0;
+                        }
+                        rank_str.push(piece_to_fen_char(piece));
+                    }
+                    None => empty_run += 1,
+                }
+            }
+            if empty_run > 0 {
+                rank_str.push_str(&empty_run.to_string());
+            }
+            ranks.push(rank_str);
+        }
+
+        ranks.join("/")
+    }
+}
+
+/// Everything `ChessBoard::make_move` destroys when it plays a move, captured so
+/// `ChessBoard::unmake_move` can restore the exact prior position without the caller having
+/// cloned the board first. Only `captured` is discovered while applying the move; the castling
+/// rights, en-passant target, and halfmove clock are state `ChessBoard` doesn't itself track, so
+/// `make_move` just carries whatever the caller passed in through to the matching
+/// `unmake_move` call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UndoInfo {
+    captured: Option<PieceType>,
+    prior_castling_rights: CastlingRights,
+    prior_en_passant_target: Option<ChessIndex>,
+    prior_halfmove_clock: u32,
 }
 
+impl UndoInfo {
+    /// The piece type captured by the move this came from (including an en-passant victim), or
+    /// `None` if nothing was captured.
+    pub fn captured(&self) -> Option<PieceType> {
+        self.captured
+    }
+
+    pub fn prior_castling_rights(&self) -> CastlingRights {
+        self.prior_castling_rights
+    }
+
+    pub fn prior_en_passant_target(&self) -> Option<ChessIndex> {
+        self.prior_en_passant_target
+    }
+
+    pub fn prior_halfmove_clock(&self) -> u32 {
+        self.prior_halfmove_clock
+    }
+}
+
+/// Centipawn value of one piece type, the material half of `ChessBoard::evaluate`'s score.
+fn material_value(piece_type: PieceType) -> i32 {
+    match piece_type {
+        PieceType::Pawn => 100,
+        PieceType::Knight => 320,
+        PieceType::Bishop => 330,
+        PieceType::Rook => 500,
+        PieceType::Queen => 900,
+        PieceType::King => 0,
+    }
+}
+
+/// The positional bonus `tables` assigns `piece` standing on `idx`. Tables are defined from
+/// White's perspective, so a Black piece's lookup mirrors `idx` vertically first, putting both
+/// colors' pawns on equal footing for, say, advancing toward the opposite back rank.
+fn square_bonus(tables: &PieceSquareTables, piece: &Piece, idx: ChessIndex) -> i32 {
+    let lookup_idx = match piece.color() {
+        Color::White => idx,
+        Color::Black => mirror_vertically(idx),
+    };
+    tables.table_for(piece.piece_type())[lookup_idx.linear_value()]
+}
+
+/// Flips `idx` to the same file on the opposite rank (rank 1 <-> 8, 2 <-> 7, ...), the mapping
+/// `square_bonus` uses to read a White-perspective table for a Black piece.
+fn mirror_vertically(idx: ChessIndex) -> ChessIndex {
+    let mirrored_rank = Rank::try_from(9 - u8::from(&idx.rank()))
+        .expect("9 minus a rank's 1..=8 value is itself in 1..=8");
+    ChessIndex::new(idx.file(), mirrored_rank)
+}
+
+/// Per-`PieceType` 64-entry positional bonus tables used by `ChessBoard::evaluate_with`, indexed
+/// via `ChessIndex::linear_value()` and defined from White's perspective (index `0` is a1, `63`
+/// is h8) — `square_bonus` mirrors them vertically to score a Black piece. `Default` gives
+/// standard values (pawns pushed toward promotion, knights penalized on the rim, the king kept
+/// behind its pawn shield); the builder setters below let a caller override one table at a time
+/// to tune the weights.
+#[derive(Debug, Clone)]
+pub struct PieceSquareTables {
+    pawn: [i32; 64],
+    knight: [i32; 64],
+    bishop: [i32; 64],
+    rook: [i32; 64],
+    queen: [i32; 64],
+    king: [i32; 64],
+}
+
+impl PieceSquareTables {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn pawn(mut self, table: [i32; 64]) -> Self {
+        self.pawn = table;
+        self
+    }
+
+    pub fn knight(mut self, table: [i32; 64]) -> Self {
+        self.knight = table;
+        self
+    }
+
+    pub fn bishop(mut self, table: [i32; 64]) -> Self {
+        self.bishop = table;
+        self
+    }
+
+    pub fn rook(mut self, table: [i32; 64]) -> Self {
+        self.rook = table;
+        self
+    }
+
+    pub fn queen(mut self, table: [i32; 64]) -> Self {
+        self.queen = table;
+        self
+    }
+
+    pub fn king(mut self, table: [i32; 64]) -> Self {
+        self.king = table;
+        self
+    }
+
+    fn table_for(&self, piece_type: PieceType) -> &[i32; 64] {
+        match piece_type {
+            PieceType::Pawn => &self.pawn,
+            PieceType::Knight => &self.knight,
+            PieceType::Bishop => &self.bishop,
+            PieceType::Rook => &self.rook,
+            PieceType::Queen => &self.queen,
+            PieceType::King => &self.king,
+        }
+    }
+}
+
+impl Default for PieceSquareTables {
+    fn default() -> Self {
+        #[rustfmt::skip]
+        let pawn = [
+            0,   0,   0,   0,   0,   0,   0,   0,
+            5,  10,  10, -20, -20,  10,  10,   5,
+            5,  -5, -10,   0,   0, -10,  -5,   5,
+            0,   0,   0,  20,  20,   0,   0,   0,
+            5,   5,  10,  25,  25,  10,   5,   5,
+            10,  10,  20,  30,  30,  20,  10,  10,
+            50,  50,  50,  50,  50,  50,  50,  50,
+            0,   0,   0,   0,   0,   0,   0,   0,
+        ];
+        #[rustfmt::skip]
+        let knight = [
+            -50, -40, -30, -30, -30, -30, -40, -50,
+            -40, -20,   0,   5,   5,   0, -20, -40,
+            -30,   5,  10,  15,  15,  10,   5, -30,
+            -30,   0,  15,  20,  20,  15,   0, -30,
+            -30,   5,  15,  20,  20,  15,   5, -30,
+            -30,   0,  10,  15,  15,  10,   0, -30,
+            -40, -20,   0,   0,   0,   0, -20, -40,
+            -50, -40, -30, -30, -30, -30, -40, -50,
+        ];
+        #[rustfmt::skip]
+        let bishop = [
+            -20, -10, -10, -10, -10, -10, -10, -20,
+            -10,   5,   0,   0,   0,   0,   5, -10,
+            -10,  10,  10,  10,  10,  10,  10, -10,
+            -10,   0,  10,  10,  10,  10,   0, -10,
+            -10,   5,   5,  10,  10,   5,   5, -10,
+            -10,   0,   5,  10,  10,   5,   0, -10,
+            -10,   0,   0,   0,   0,   0,   0, -10,
+            -20, -10, -10, -10, -10, -10, -10, -20,
+        ];
+        #[rustfmt::skip]
+        let rook = [
+            0,   0,   0,   5,   5,   0,   0,   0,
+            -5,   0,   0,   0,   0,   0,   0,  -5,
+            -5,   0,   0,   0,   0,   0,   0,  -5,
+            -5,   0,   0,   0,   0,   0,   0,  -5,
+            -5,   0,   0,   0,   0,   0,   0,  -5,
+            -5,   0,   0,   0,   0,   0,   0,  -5,
+            5,  10,  10,  10,  10,  10,  10,   5,
+            0,   0,   0,   0,   0,   0,   0,   0,
+        ];
+        #[rustfmt::skip]
+        let queen = [
+            -20, -10, -10,  -5,  -5, -10, -10, -20,
+            -10,   0,   5,   0,   0,   0,   0, -10,
+            -10,   5,   5,   5,   5,   5,   0, -10,
+            0,   0,   5,   5,   5,   5,   0,  -5,
+            -5,   0,   5,   5,   5,   5,   0,  -5,
+            -10,   0,   5,   5,   5,   5,   0, -10,
+            -10,   0,   0,   0,   0,   0,   0, -10,
+            -20, -10, -10,  -5,  -5, -10, -10, -20,
+        ];
+        #[rustfmt::skip]
+        let king = [
+            20,  30,  10,   0,   0,  10,  30,  20,
+            20,  20,   0,   0,   0,   0,  20,  20,
+            -10, -20, -20, -20, -20, -20, -20, -10,
+            -20, -30, -30, -40, -40, -30, -30, -20,
+            -30, -40, -40, -50, -50, -40, -40, -30,
+            -30, -40, -40, -50, -50, -40, -40, -30,
+            -30, -40, -40, -50, -50, -40, -40, -30,
+            -30, -40, -40, -50, -50, -40, -40, -30,
+        ];
+
+        Self {
+            pawn,
+            knight,
+            bishop,
+            rook,
+            queen,
+            king,
+        }
+    }
+}
+
+fn piece_to_fen_char(piece: &Piece) -> char {
+    let c = match piece.piece_type() {
+        PieceType::Pawn => 'p',
+        PieceType::Knight => 'n',
+        PieceType::Bishop => 'b',
+        PieceType::Rook => 'r',
+        PieceType::Queen => 'q',
+        PieceType::King => 'k',
+    };
+    match piece.color() {
+        Color::White => c.to_ascii_uppercase(),
+        Color::Black => c,
+    }
+}
+
+impl FromStr for ChessBoard {
+    type Err = FenError;
+
+    /// Parses the piece-placement field of FEN (e.g. the part before the first space in a
+    /// full FEN string).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fen_ranks: Vec<&str> = s.split('/').collect();
+        if fen_ranks.len() != 8 {
+            return Err(FenError::WrongRankCount(fen_ranks.len()));
+        }
+
+        let mut board = ChessBoard::default();
+
+        // FEN lists ranks from 8 down to 1
+        for (fen_rank, rank) in fen_ranks.iter().zip(RankIter::start_at(Rank::Eighth).rev()) {
+            let mut file_iter = FileIter::start_at(File::A);
+            for c in fen_rank.chars() {
+                if let Some(digit) = c.to_digit(10) {
+                    for _ in 0..digit {
+                        file_iter.next().ok_or(FenError::TooManyFilesInRank)?;
+                    }
+                } else {
+                    let file = file_iter.next().ok_or(FenError::TooManyFilesInRank)?;
+                    let piece = piece_from_fen_char(c).ok_or(FenError::InvalidPieceChar(c))?;
+                    board.set_piece(ChessIndex::new(file, rank), piece);
+                }
+            }
+        }
+
+        Ok(board)
+    }
+}
+
+fn piece_from_fen_char(c: char) -> Option<Piece> {
+    let color = if c.is_ascii_uppercase() {
+        Color::White
+    } else {
+        Color::Black
+    };
+    let piece_type = match c.to_ascii_lowercase() {
+        'p' => PieceType::Pawn,
+        'n' => PieceType::Knight,
+        'b' => PieceType::Bishop,
+        'r' => PieceType::Rook,
+        'q' => PieceType::Queen,
+        'k' => PieceType::King,
+        _ => return None,
+    };
+    Some(Piece::new(piece_type, color))
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FenError {
+    WrongRankCount(usize),
+    TooManyFilesInRank,
+    InvalidPieceChar(char),
+    InvalidField(String),
+    InvalidPosition(InvalidError),
+}
+
+impl Display for FenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let output = match self {
+            FenError::WrongRankCount(count) => {
+                format!("expected 8 ranks in FEN piece placement, found {}", count)
+            }
+            FenError::TooManyFilesInRank => {
+                format!("a FEN rank described more than 8 files")
+            }
+            FenError::InvalidPieceChar(c) => format!("invalid piece character: '{}'", c),
+            FenError::InvalidField(field) => format!("invalid FEN field: '{}'", field),
+            FenError::InvalidPosition(err) => format!("invalid position: {}", err),
+        };
+
+        write!(f, "{}", output)
+    }
+}
+
+impl From<InvalidError> for FenError {
+    fn from(err: InvalidError) -> Self {
+        FenError::InvalidPosition(err)
+    }
+}
+
+impl Error for FenError {}
+
 impl Display for ChessBoard {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut lines = Vec::new();
@@ -176,4 +1036,323 @@ mod tests {
 
         assert!(board.take_piece(E2).unwrap().is_pawn());
     }
+
+    #[test]
+    fn test_fen_round_trip() {
+        let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR";
+
+        let board = ChessBoard::from_str(fen).unwrap();
+
+        assert_eq!(board.to_fen(), fen);
+    }
+
+    #[test]
+    fn test_fen_round_trip_with_empty_squares() {
+        let fen = "r3k2r/8/8/8/8/8/8/R3K2R";
+
+        let board = ChessBoard::from_str(fen).unwrap();
+
+        assert_eq!(board.to_fen(), fen);
+    }
+
+    #[test]
+    fn test_starting_position_matches_fen() {
+        let board = ChessBoard::starting_position();
+
+        assert_eq!(board.to_fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR");
+    }
+
+    #[test]
+    fn test_is_attacked_by_knight() {
+        let mut board = ChessBoard::default();
+        board.set_piece(B1, Piece::knight(Color::White));
+
+        assert!(board.is_attacked(D2, Color::White));
+        assert!(board.is_attacked(A3, Color::White));
+        assert!(!board.is_attacked(B1, Color::White));
+        assert!(!board.is_attacked(D2, Color::Black));
+    }
+
+    #[test]
+    fn test_is_attacked_by_pawn() {
+        let mut board = ChessBoard::default();
+        board.set_piece(D4, Piece::pawn(Color::White));
+
+        assert!(board.is_attacked(C5, Color::White));
+        assert!(board.is_attacked(E5, Color::White));
+        assert!(!board.is_attacked(D5, Color::White)); // a pawn doesn't attack its own push
+    }
+
+    #[test]
+    fn test_legal_moves_knight_in_center() {
+        let mut board = ChessBoard::default();
+        board.set_piece(E4, Piece::knight(Color::White));
+
+        let mut destinations: Vec<ChessIndex> = board
+            .legal_moves(E4, Color::White)
+            .into_iter()
+            .map(|m| match m {
+                ChessMove::Regular(m) => m.to_idx(),
+                _ => panic!("a knight move is always a regular move"),
+            })
+            .collect();
+        destinations.sort_by_key(|idx| idx.to_string());
+
+        let mut expected = vec![D2, F2, C3, G3, C5, G5, D6, F6];
+        expected.sort_by_key(|idx| idx.to_string());
+
+        assert_eq!(destinations, expected);
+    }
+
+    #[test]
+    fn test_legal_moves_sliding_piece_stops_at_a_blocker() {
+        let mut board = ChessBoard::default();
+        board.set_piece(A1, Piece::rook(Color::White));
+        board.set_piece(A4, Piece::pawn(Color::Black));
+
+        let destinations: Vec<ChessIndex> = board
+            .legal_moves(A1, Color::White)
+            .into_iter()
+            .map(|m| match m {
+                ChessMove::Regular(m) => m.to_idx(),
+                _ => panic!("a rook move is always a regular move"),
+            })
+            .collect();
+
+        assert!(destinations.contains(&A4)); // can capture the blocker
+        assert!(!destinations.contains(&A5)); // but not jump over it
+    }
+
+    #[test]
+    fn test_legal_moves_pawn_double_push_and_capture() {
+        let mut board = ChessBoard::default();
+        board.set_piece(E2, Piece::pawn(Color::White));
+        board.set_piece(D3, Piece::pawn(Color::Black));
+
+        let destinations: Vec<ChessIndex> = board
+            .legal_moves(E2, Color::White)
+            .into_iter()
+            .map(|m| match m {
+                ChessMove::Regular(m) => m.to_idx(),
+                _ => panic!("none of these moves reach the back rank"),
+            })
+            .collect();
+
+        assert!(destinations.contains(&E3));
+        assert!(destinations.contains(&E4));
+        assert!(destinations.contains(&D3));
+    }
+
+    #[test]
+    fn test_legal_moves_pawn_promotes_on_the_back_rank() {
+        let mut board = ChessBoard::default();
+        board.set_piece(E7, Piece::pawn(Color::White));
+
+        let moves = board.legal_moves(E7, Color::White);
+        assert_eq!(moves.len(), 4);
+        assert!(moves
+            .iter()
+            .all(|m| matches!(m, ChessMove::Promotion(m) if m.to_idx() == E8)));
+    }
+
+    #[test]
+    fn test_legal_moves_en_passant_capture() {
+        let mut board = ChessBoard::default();
+        board.set_piece(E5, Piece::pawn(Color::White));
+        // moving this pawn directly to D5 (rather than playing D7-D5 through `make_move`)
+        // still leaves it with the two-entry history `en_passant_moves` looks for: its placement
+        // square followed by the square it's "arrived" on.
+        let mut black_pawn = Piece::pawn(Color::Black);
+        black_pawn.add_index_to_history(D7);
+        black_pawn.add_index_to_history(D5);
+        board[D5].set_piece(black_pawn);
+
+        let moves = board.legal_moves(E5, Color::White);
+        assert!(moves.contains(&ChessMove::en_passant(E5, D6, D5)));
+    }
+
+    #[test]
+    fn test_legal_moves_king_excludes_squares_that_stay_in_check() {
+        let mut board = ChessBoard::default();
+        board.set_piece(E1, Piece::king(Color::White));
+        board.set_piece(E8, Piece::rook(Color::Black));
+
+        let destinations: Vec<ChessIndex> = board
+            .legal_moves(E1, Color::White)
+            .into_iter()
+            .map(|m| match m {
+                ChessMove::Regular(m) => m.to_idx(),
+                _ => panic!("a king move off the back rank is always regular here"),
+            })
+            .collect();
+
+        // every square on the e-file stays in the rook's line of check
+        assert!(!destinations.contains(&E2));
+        assert!(destinations.contains(&D1));
+        assert!(destinations.contains(&D2));
+        assert!(destinations.contains(&F1));
+        assert!(destinations.contains(&F2));
+    }
+
+    #[test]
+    fn test_legal_moves_castle_kingside() {
+        let mut board = ChessBoard::default();
+        board.set_piece(E1, Piece::king(Color::White));
+        board.set_piece(H1, Piece::rook(Color::White));
+
+        let moves = board.legal_moves(E1, Color::White);
+        assert!(moves.contains(&ChessMove::castle(E1, G1, H1, F1)));
+    }
+
+    #[test]
+    fn test_legal_moves_castle_blocked_by_check() {
+        let mut board = ChessBoard::default();
+        board.set_piece(E1, Piece::king(Color::White));
+        board.set_piece(H1, Piece::rook(Color::White));
+        board.set_piece(F8, Piece::rook(Color::Black)); // attacks f1, the king's castling path
+
+        let moves = board.legal_moves(E1, Color::White);
+        assert!(!moves.contains(&ChessMove::castle(E1, G1, H1, F1)));
+    }
+
+    #[test]
+    fn test_legal_moves_castle_unavailable_once_rook_has_moved() {
+        let mut board = ChessBoard::default();
+        board.set_piece(E1, Piece::king(Color::White));
+        let mut rook = Piece::rook(Color::White);
+        rook.add_index_to_history(A1);
+        rook.add_index_to_history(H1);
+        board[H1].set_piece(rook);
+
+        let moves = board.legal_moves(E1, Color::White);
+        assert!(!moves.contains(&ChessMove::castle(E1, G1, H1, F1)));
+    }
+
+    #[test]
+    fn test_moves_for_filters_out_moves_that_leave_the_king_in_check() {
+        let mut board = ChessBoard::default();
+        board.set_piece(E1, Piece::king(Color::White));
+        board.set_piece(D2, Piece::knight(Color::White));
+        board.set_piece(C3, Piece::bishop(Color::Black)); // pins the knight along the e1-c3 diagonal
+
+        let moves = board.moves_for(Color::White);
+        assert!(!moves
+            .iter()
+            .any(|m| matches!(m, ChessMove::Regular(m) if m.from_idx() == D2)));
+    }
+
+    #[test]
+    fn test_is_in_check() {
+        let mut board = ChessBoard::default();
+        board.set_piece(E1, Piece::king(Color::White));
+        assert!(!board.is_in_check(Color::White));
+
+        board.set_piece(E8, Piece::rook(Color::Black));
+        assert!(board.is_in_check(Color::White));
+    }
+
+    #[test]
+    fn test_zobrist_hash_ignores_move_order() {
+        // the same final placement reached via two different sequences of set_piece calls
+        let mut board_a = ChessBoard::default();
+        board_a.set_piece(E4, Piece::pawn(Color::White));
+        board_a.set_piece(D5, Piece::pawn(Color::Black));
+
+        let mut board_b = ChessBoard::default();
+        board_b.set_piece(D5, Piece::pawn(Color::Black));
+        board_b.set_piece(E4, Piece::pawn(Color::White));
+
+        assert_eq!(board_a.zobrist_hash(), board_b.zobrist_hash());
+    }
+
+    #[test]
+    fn test_zobrist_hash_changes_with_a_single_piece() {
+        let mut board = ChessBoard::default();
+        board.set_piece(E4, Piece::pawn(Color::White));
+        let before = board.zobrist_hash();
+
+        board.set_piece(D5, Piece::pawn(Color::Black));
+
+        assert_ne!(before, board.zobrist_hash());
+    }
+
+    #[test]
+    fn test_evaluate_starting_position_is_symmetric() {
+        let board = ChessBoard::starting_position();
+
+        assert_eq!(board.evaluate(Color::White), 0);
+        assert_eq!(board.evaluate(Color::Black), 0);
+    }
+
+    #[test]
+    fn test_evaluate_rewards_a_centralized_knight() {
+        let mut rim_board = ChessBoard::default();
+        rim_board.set_piece(A1, Piece::knight(Color::White));
+
+        let mut center_board = ChessBoard::default();
+        center_board.set_piece(D4, Piece::knight(Color::White));
+
+        assert!(center_board.evaluate(Color::White) > rim_board.evaluate(Color::White));
+    }
+
+    #[test]
+    fn test_make_move_then_unmake_move_restores_a_regular_capture() {
+        let mut board = ChessBoard::default();
+        board.set_piece(E4, Piece::pawn(Color::White));
+        board.set_piece(D5, Piece::pawn(Color::Black));
+        let before = board.to_fen();
+
+        let rights = CastlingRights::default();
+        let undo = board.make_move(ChessMove::regular(E4, D5), rights, None, 0);
+        assert!(board.piece_at(E4).is_none());
+        assert_eq!(board.piece_at(D5).unwrap().color(), Color::White);
+        assert_eq!(undo.captured(), Some(PieceType::Pawn));
+
+        let (restored_rights, restored_ep, restored_clock) =
+            board.unmake_move(ChessMove::regular(E4, D5), undo);
+        assert_eq!(restored_rights, rights);
+        assert_eq!(restored_ep, None);
+        assert_eq!(restored_clock, 0);
+        assert_eq!(board.to_fen(), before);
+    }
+
+    #[test]
+    fn test_make_move_then_unmake_move_restores_an_en_passant_capture() {
+        let mut board = ChessBoard::default();
+        board.set_piece(E5, Piece::pawn(Color::White));
+        board.set_piece(D5, Piece::pawn(Color::Black));
+        let before = board.to_fen();
+
+        let undo = board.make_move(
+            ChessMove::en_passant(E5, D6, D5),
+            CastlingRights::default(),
+            Some(D6),
+            0,
+        );
+        assert!(board.piece_at(D5).is_none());
+        assert_eq!(board.piece_at(D6).unwrap().color(), Color::White);
+
+        board.unmake_move(ChessMove::en_passant(E5, D6, D5), undo);
+        assert_eq!(board.to_fen(), before);
+    }
+
+    #[test]
+    fn test_make_move_then_unmake_move_restores_a_promotion() {
+        let mut board = ChessBoard::default();
+        board.set_piece(E7, Piece::pawn(Color::White));
+        board.set_piece(D8, Piece::rook(Color::Black));
+        let before = board.to_fen();
+
+        let undo = board.make_move(
+            ChessMove::promotion(E7, D8, PieceType::Queen),
+            CastlingRights::default(),
+            None,
+            0,
+        );
+        assert!(board.piece_at(D8).unwrap().is_queen());
+        assert_eq!(undo.captured(), Some(PieceType::Rook));
+
+        board.unmake_move(ChessMove::promotion(E7, D8, PieceType::Queen), undo);
+        assert_eq!(board.to_fen(), before);
+    }
 }
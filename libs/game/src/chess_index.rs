@@ -2,7 +2,7 @@ use std::{convert::TryFrom, error::Error, fmt::Display, str::FromStr};
 
 use crate::{File, FileIter, Rank, RankIter};
 
-#[derive(Copy, Clone, PartialEq, Debug, Eq)]
+#[derive(Copy, Clone, PartialEq, Debug, Eq, serde::Serialize, serde::Deserialize)]
 pub struct ChessIndex(pub(crate) File, pub(crate) Rank);
 
 impl ChessIndex {
@@ -14,6 +14,16 @@ impl ChessIndex {
         (8 * (u8::from(&self.rank()) - 1) + (u8::from(&self.file()) - 1)) as usize
     }
 
+    /// The inverse of `linear_value`: the square whose `rank*8 + file` bit index (`a1` = 0,
+    /// `h8` = 63) is `value`. Used to turn a set `Bitboard` bit back into a `ChessIndex`.
+    pub(crate) fn from_linear_value(value: usize) -> ChessIndex {
+        let file = File::try_from((value % 8) as u8 + 1)
+            .expect("linear value's file component is always in range 0..8");
+        let rank = Rank::try_from((value / 8) as u8 + 1)
+            .expect("linear value's rank component is always in range 0..8");
+        ChessIndex::new(file, rank)
+    }
+
     pub fn rank(&self) -> Rank {
         self.1
     }
@@ -60,17 +70,132 @@ impl ChessIndex {
                     .collect();
             }
         } else {
-            vec![]
+            // diagonal: equal absolute file- and rank-delta, walked one step at a time
+            let file_delta = i32::from(u8::from(&to.file())) - i32::from(u8::from(&from.file()));
+            let rank_delta = i32::from(&to.rank()) - i32::from(&from.rank());
+            if file_delta.abs() != rank_delta.abs() {
+                return vec![];
+            }
+
+            let file_ascending = file_delta > 0;
+            let rank_ascending = rank_delta > 0;
+
+            let mut indices = Vec::new();
+            let mut current = from;
+            loop {
+                indices.push(current);
+                if current == to {
+                    break;
+                }
+                let next_file = if file_ascending {
+                    current.file() + 1
+                } else {
+                    current.file() - 1
+                };
+                let next_rank = if rank_ascending {
+                    current.rank() + 1
+                } else {
+                    current.rank() - 1
+                };
+                current = ChessIndex::new(
+                    next_file.expect("diagonal walk stays on the board"),
+                    next_rank.expect("diagonal walk stays on the board"),
+                );
+            }
+            indices
+        }
+    }
+
+    /// `self` stepped `file_delta` files and `rank_delta` ranks at once, or `None` if either
+    /// axis would leave the board. Widens both deltas to `i32` and defers to `offset_checked`.
+    pub fn offset(&self, file_delta: i8, rank_delta: i8) -> Option<ChessIndex> {
+        self.offset_checked(file_delta as i32, rank_delta as i32)
+    }
+}
+
+/// The eight `(file_delta, rank_delta)` offsets a knight can jump to from any square, in no
+/// particular order.
+pub const KNIGHT_DELTAS: [(i8, i8); 8] = [
+    (2, 1),
+    (2, -1),
+    (-2, 1),
+    (-2, -1),
+    (1, 2),
+    (1, -2),
+    (-1, 2),
+    (-1, -2),
+];
+
+/// The eight `(file_delta, rank_delta)` directions a king steps one square along, or a queen
+/// slides any number of squares along: the rook's four orthogonal directions plus the bishop's
+/// four diagonals. Pairs with `RayIter` to walk a whole ray in one of these directions.
+pub const QUEEN_DIRECTIONS: [(i8, i8); 8] = [
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+/// The squares encountered walking from (but not including) a start square along a fixed
+/// `(file_delta, rank_delta)` direction until stepping off the board. Built on `ChessIndex::offset`,
+/// this is the direction-at-a-time geometry primitive a sliding-piece move generator walks one
+/// ray at a time; it doesn't know about occupancy, so a caller that needs to stop at the first
+/// blocker still has to do that themselves (see `bitboard::ray_attacks` for that variant).
+pub struct RayIter {
+    current: ChessIndex,
+    direction: (i8, i8),
+}
+
+impl RayIter {
+    pub fn new(start: ChessIndex, direction: (i8, i8)) -> Self {
+        Self {
+            current: start,
+            direction,
         }
     }
 }
 
+impl Iterator for RayIter {
+    type Item = ChessIndex;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.current.offset(self.direction.0, self.direction.1)?;
+        self.current = next;
+        Some(next)
+    }
+}
+
 impl From<(File, Rank)> for ChessIndex {
     fn from((file, rank): (File, Rank)) -> Self {
         ChessIndex::new(file, rank)
     }
 }
 
+/// `idx`'s single set bit in the `a1` = bit 0, `h8` = bit 63 ordering `ChessBoard` and
+/// `Bitboard` use internally.
+impl From<ChessIndex> for u64 {
+    fn from(idx: ChessIndex) -> Self {
+        1u64 << idx.linear_value()
+    }
+}
+
+/// The inverse of `From<ChessIndex> for u64`: succeeds only for a mask with exactly one bit
+/// set, mapping it back to the square at that bit position.
+impl TryFrom<u64> for ChessIndex {
+    type Error = ();
+
+    fn try_from(mask: u64) -> Result<Self, Self::Error> {
+        if mask.count_ones() != 1 {
+            return Err(());
+        }
+        Ok(ChessIndex::from_linear_value(mask.trailing_zeros() as usize))
+    }
+}
+
 impl TryFrom<(i32, i32)> for ChessIndex {
     type Error = ();
     fn try_from((file, rank): (i32, i32)) -> Result<Self, Self::Error> {
@@ -138,11 +263,69 @@ mod tests {
     fn test_indices_between() {
         assert_eq!(ChessIndex::indices_between(E4, E7), vec![E4, E5, E6, E7]);
         assert_eq!(ChessIndex::indices_between(E7, E4), vec![E7, E6, E5, E4]);
-        assert_eq!(ChessIndex::indices_between(E4, F3), vec![]);
+        assert_eq!(ChessIndex::indices_between(E4, G3), vec![]);
         assert_eq!(ChessIndex::indices_between(A1, D1), vec![A1, B1, C1, D1]);
         assert_eq!(
             ChessIndex::indices_between(E1, A1),
             vec![E1, D1, C1, B1, A1]
         );
     }
+
+    #[test]
+    fn test_indices_between_diagonal() {
+        // ascending diagonal (file and rank both increasing)
+        assert_eq!(
+            ChessIndex::indices_between(B2, E5),
+            vec![B2, C3, D4, E5]
+        );
+        // descending diagonal (file and rank both decreasing)
+        assert_eq!(
+            ChessIndex::indices_between(E5, B2),
+            vec![E5, D4, C3, B2]
+        );
+        // anti-diagonal (file increasing, rank decreasing)
+        assert_eq!(ChessIndex::indices_between(E4, F3), vec![E4, F3]);
+        // non-aligned pair still yields nothing
+        assert_eq!(ChessIndex::indices_between(A1, B3), vec![]);
+    }
+
+    #[test]
+    fn test_u64_round_trip() {
+        assert_eq!(u64::from(A1), 1);
+        assert_eq!(u64::from(H8), 1u64 << 63);
+        assert_eq!(ChessIndex::try_from(u64::from(E4)), Ok(E4));
+        assert_eq!(ChessIndex::try_from(0u64), Err(()));
+        assert_eq!(ChessIndex::try_from(0b11u64), Err(()));
+    }
+
+    #[test]
+    fn test_offset() {
+        assert_eq!(E4.offset(1, 1), Some(F5));
+        assert_eq!(E4.offset(-2, 1), Some(C5));
+        assert_eq!(H8.offset(1, 0), None);
+        assert_eq!(A1.offset(-1, 0), None);
+    }
+
+    #[test]
+    fn test_ray_iter() {
+        let mut ray = RayIter::new(A1, (1, 1));
+        assert_eq!(ray.next(), Some(B2));
+        assert_eq!(ray.next(), Some(C3));
+        assert_eq!(ray.collect::<Vec<_>>(), vec![D4, E5, F6, G7, H8]);
+
+        let mut edge_ray = RayIter::new(H8, (1, 0));
+        assert_eq!(edge_ray.next(), None);
+    }
+
+    #[test]
+    fn test_knight_deltas_and_queen_directions_stay_on_board_from_the_center() {
+        assert_eq!(KNIGHT_DELTAS.len(), 8);
+        assert_eq!(QUEEN_DIRECTIONS.len(), 8);
+        for &(file_delta, rank_delta) in KNIGHT_DELTAS.iter() {
+            assert!(D4.offset(file_delta, rank_delta).is_some());
+        }
+        for &(file_delta, rank_delta) in QUEEN_DIRECTIONS.iter() {
+            assert!(D4.offset(file_delta, rank_delta).is_some());
+        }
+    }
 }
@@ -1,4 +1,6 @@
-use crate::{ChessIndex, PieceType};
+use std::{convert::TryFrom, fmt::Display, str::FromStr};
+
+use crate::{ChessIndex, File, Game, PieceType, Rank};
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 pub enum ChessMove {
@@ -32,6 +34,371 @@ impl ChessMove {
     pub fn en_passant(from: ChessIndex, to: ChessIndex, taken_pawn_idx: ChessIndex) -> ChessMove {
         ChessMove::EnPassant(EnPassantMove::new(from, to, taken_pawn_idx))
     }
+
+    pub fn castle(
+        king_from: ChessIndex,
+        king_to: ChessIndex,
+        rook_from: ChessIndex,
+        rook_to: ChessIndex,
+    ) -> ChessMove {
+        ChessMove::Castle(CastleMove::new(king_from, king_to, rook_from, rook_to))
+    }
+
+    /// Long-algebraic UCI notation, e.g. `e2e4`, `e7e8q` for a queen promotion. Castling is
+    /// expressed as the king's own move (`e1g1`), matching what UCI engines send and expect.
+    pub fn to_uci(&self) -> String {
+        match self {
+            ChessMove::Regular(m) => format!("{}{}", m.from_idx(), m.to_idx()),
+            ChessMove::Castle(m) => format!("{}{}", m.king_from(), m.king_to()),
+            ChessMove::EnPassant(m) => format!("{}{}", m.from_idx(), m.to_idx()),
+            ChessMove::Promotion(m) => format!(
+                "{}{}{}",
+                m.from_idx(),
+                m.to_idx(),
+                promotion_uci_char(m.promotion_piece())
+            ),
+        }
+    }
+
+    /// Parses long-algebraic UCI notation (`e2e4`, `e7e8q`) into a concrete `ChessMove`,
+    /// resolving it against `game.valid_moves_from(from)` the same way `Game::make_move`
+    /// resolves a bare destination square. Castling and en passant can't be told apart from a
+    /// regular move by coordinates alone, which is why this needs `game` rather than being a
+    /// plain `FromStr` impl.
+    pub fn from_uci(uci: &str, game: &Game) -> Result<ChessMove, ParseUciError> {
+        if uci.len() != 4 && uci.len() != 5 {
+            return Err(ParseUciError::WrongLength(uci.len()));
+        }
+
+        let from = ChessIndex::from_str(&uci[0..2])
+            .map_err(|_| ParseUciError::InvalidSquare(uci[0..2].to_string()))?;
+        let to = ChessIndex::from_str(&uci[2..4])
+            .map_err(|_| ParseUciError::InvalidSquare(uci[2..4].to_string()))?;
+
+        let promotion = match uci.as_bytes().get(4) {
+            Some(&c) => Some(
+                promotion_piece_from_uci_char(c as char)
+                    .ok_or(ParseUciError::InvalidPromotionPiece(c as char))?,
+            ),
+            None => None,
+        };
+
+        game.valid_moves_from(from)
+            .into_iter()
+            .find(|candidate| move_matches(candidate, to, promotion))
+            .ok_or(ParseUciError::NoSuchMove)
+    }
+
+    /// Standard Algebraic Notation for this move (`Nf3`, `exd5`, `O-O`, `e8=Q+`, `Qxf7#`), called
+    /// with `game` still at the position this move is about to be played from (the same
+    /// convention `Game::execute_move` uses). Disambiguation and the check/mate suffix are worked
+    /// out against `game.legal_moves()`, so this only makes sense for a move that's actually
+    /// legal there.
+    pub fn to_san(&self, game: &Game) -> String {
+        if let ChessMove::Castle(castle_move) = self {
+            let mut san = if castle_move.king_to().file() > castle_move.king_from().file() {
+                "O-O".to_string()
+            } else {
+                "O-O-O".to_string()
+            };
+            san.push_str(&check_or_mate_suffix(game, *self));
+            return san;
+        }
+
+        let (from, to) = match self {
+            ChessMove::Regular(m) => (m.from_idx(), m.to_idx()),
+            ChessMove::Promotion(m) => (m.from_idx(), m.to_idx()),
+            ChessMove::EnPassant(m) => (m.from_idx(), m.to_idx()),
+            ChessMove::Castle(_) => unreachable!("castling was handled above"),
+        };
+        let piece_type = game.board[from]
+            .piece()
+            .expect("a move must start on an occupied square")
+            .piece_type();
+        let is_capture =
+            game.board[to].piece().is_some() || matches!(self, ChessMove::EnPassant(_));
+
+        let mut san = String::new();
+        if piece_type == PieceType::Pawn {
+            if is_capture {
+                san.push(char::from(&from.file()));
+            }
+        } else {
+            san.push(san_piece_letter(piece_type));
+            san.push_str(&disambiguation(game, from, to, piece_type));
+        }
+
+        if is_capture {
+            san.push('x');
+        }
+        san.push_str(&to.to_string());
+
+        if let ChessMove::Promotion(promotion_move) = self {
+            san.push('=');
+            san.push(san_piece_letter(promotion_move.promotion_piece()));
+        }
+
+        san.push_str(&check_or_mate_suffix(game, *self));
+        san
+    }
+
+    /// Parses Standard Algebraic Notation (`Nf3`, `exd5`, `O-O`, `e8=Q+`, `Qxf7#`) into a
+    /// concrete `ChessMove`, resolving any ambiguity against `game.legal_moves()` the same way
+    /// `to_san` generates disambiguation from it.
+    pub fn from_san(san: &str, game: &Game) -> Result<ChessMove, ParseSanError> {
+        let trimmed = san.trim_end_matches(|c| c == '+' || c == '#');
+
+        if trimmed == "O-O" || trimmed == "0-0" {
+            return game
+                .legal_moves()
+                .into_iter()
+                .find(|m| {
+                    matches!(m, ChessMove::Castle(c) if c.king_to().file() > c.king_from().file())
+                })
+                .ok_or(ParseSanError::NoSuchMove);
+        }
+        if trimmed == "O-O-O" || trimmed == "0-0-0" {
+            return game
+                .legal_moves()
+                .into_iter()
+                .find(|m| {
+                    matches!(m, ChessMove::Castle(c) if c.king_to().file() < c.king_from().file())
+                })
+                .ok_or(ParseSanError::NoSuchMove);
+        }
+
+        let mut chars: Vec<char> = trimmed.chars().collect();
+
+        let promotion = match chars.iter().position(|&c| c == '=') {
+            Some(eq_pos) => {
+                let promotion_char = *chars
+                    .get(eq_pos + 1)
+                    .ok_or_else(|| ParseSanError::Malformed(trimmed.to_string()))?;
+                let piece = san_piece_from_letter(promotion_char)
+                    .ok_or(ParseSanError::InvalidPiece(promotion_char))?;
+                chars.truncate(eq_pos);
+                Some(piece)
+            }
+            None => None,
+        };
+
+        let piece_type = match chars.first() {
+            Some(&c) if c.is_ascii_uppercase() => {
+                let piece = san_piece_from_letter(c).ok_or(ParseSanError::InvalidPiece(c))?;
+                chars.remove(0);
+                piece
+            }
+            _ => PieceType::Pawn,
+        };
+
+        chars.retain(|&c| c != 'x');
+
+        if chars.len() < 2 {
+            return Err(ParseSanError::Malformed(trimmed.to_string()));
+        }
+
+        let to_str: String = chars[chars.len() - 2..].iter().collect();
+        let to = ChessIndex::from_str(&to_str)
+            .map_err(|_| ParseSanError::Malformed(trimmed.to_string()))?;
+
+        let disambiguation = &chars[..chars.len() - 2];
+        let disambiguation_file = disambiguation.iter().find_map(|&c| File::try_from(c).ok());
+        let disambiguation_rank = disambiguation.iter().find_map(|&c| Rank::try_from(c).ok());
+
+        game.legal_moves()
+            .into_iter()
+            .find(|candidate| {
+                let (from, candidate_to, candidate_promotion) = match candidate {
+                    ChessMove::Regular(m) => (m.from_idx(), m.to_idx(), None),
+                    ChessMove::Promotion(m) => {
+                        (m.from_idx(), m.to_idx(), Some(m.promotion_piece()))
+                    }
+                    ChessMove::EnPassant(m) => (m.from_idx(), m.to_idx(), None),
+                    ChessMove::Castle(_) => return false,
+                };
+                let candidate_piece_type = game.board[from]
+                    .piece()
+                    .map(|p| p.piece_type())
+                    .unwrap_or(PieceType::Pawn);
+
+                candidate_to == to
+                    && candidate_piece_type == piece_type
+                    && candidate_promotion == promotion
+                    && disambiguation_file.map_or(true, |f| from.file() == f)
+                    && disambiguation_rank.map_or(true, |r| from.rank() == r)
+            })
+            .ok_or(ParseSanError::NoSuchMove)
+    }
+}
+
+/// The file/rank/full-square prefix `ChessMove::to_san` needs to disambiguate `from` from any
+/// other legal move of the same `piece_type` landing on `to` — empty if there's no such competing
+/// move.
+fn disambiguation(game: &Game, from: ChessIndex, to: ChessIndex, piece_type: PieceType) -> String {
+    let color = game.board[from]
+        .piece()
+        .expect("a move must start on an occupied square")
+        .color();
+
+    let competitors: Vec<ChessIndex> = game
+        .legal_moves()
+        .into_iter()
+        .filter_map(|candidate| match candidate {
+            ChessMove::Regular(m) if m.to_idx() == to && m.from_idx() != from => Some(m.from_idx()),
+            _ => None,
+        })
+        .filter(|&idx| {
+            game.board[idx]
+                .piece()
+                .map(|p| p.piece_type() == piece_type && p.color() == color)
+                .unwrap_or(false)
+        })
+        .collect();
+
+    if competitors.is_empty() {
+        return String::new();
+    }
+
+    let same_file = competitors.iter().any(|idx| idx.file() == from.file());
+    let same_rank = competitors.iter().any(|idx| idx.rank() == from.rank());
+
+    if !same_file {
+        from.file().to_string()
+    } else if !same_rank {
+        from.rank().to_string()
+    } else {
+        from.to_string()
+    }
+}
+
+/// The `+`/`#` suffix `ChessMove::to_san` appends: plays `chess_move` out on a scratch clone of
+/// `game` and checks the responding side the same way `Game::make_move` derives `MoveOutcome`.
+fn check_or_mate_suffix(game: &Game, chess_move: ChessMove) -> String {
+    let mut clone = game.clone();
+    clone.execute_move(chess_move);
+
+    let side_to_respond = clone.side_to_move;
+    let in_check = clone.is_king_checked(side_to_respond);
+    let has_legal_moves = !clone.legal_moves().is_empty();
+
+    match (has_legal_moves, in_check) {
+        (false, true) => "#".to_string(),
+        (true, true) => "+".to_string(),
+        _ => String::new(),
+    }
+}
+
+/// The uppercase letter SAN uses for a piece, e.g. in `Nf3` or the `Q` in `e8=Q`. Pawns have no
+/// SAN letter, so `ChessMove::to_san` never calls this for `PieceType::Pawn`.
+fn san_piece_letter(piece_type: PieceType) -> char {
+    match piece_type {
+        PieceType::Knight => 'N',
+        PieceType::Bishop => 'B',
+        PieceType::Rook => 'R',
+        PieceType::Queen => 'Q',
+        PieceType::King => 'K',
+        PieceType::Pawn => panic!("pawns have no SAN piece letter"),
+    }
+}
+
+/// The inverse of `san_piece_letter`.
+fn san_piece_from_letter(c: char) -> Option<PieceType> {
+    match c {
+        'N' => Some(PieceType::Knight),
+        'B' => Some(PieceType::Bishop),
+        'R' => Some(PieceType::Rook),
+        'Q' => Some(PieceType::Queen),
+        'K' => Some(PieceType::King),
+        _ => None,
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseSanError {
+    Malformed(String),
+    InvalidPiece(char),
+    NoSuchMove,
+}
+
+impl Display for ParseSanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let output = match self {
+            ParseSanError::Malformed(san) => format!("malformed SAN move: '{}'", san),
+            ParseSanError::InvalidPiece(c) => format!("invalid piece letter: '{}'", c),
+            ParseSanError::NoSuchMove => format!("that move isn't legal in this position"),
+        };
+
+        write!(f, "{}", output)
+    }
+}
+
+/// The lowercase promotion-piece suffix UCI appends to a promotion move (e.g. the `q` in
+/// `e7e8q`).
+fn promotion_uci_char(piece_type: PieceType) -> char {
+    match piece_type {
+        PieceType::Knight => 'n',
+        PieceType::Bishop => 'b',
+        PieceType::Rook => 'r',
+        PieceType::Queen => 'q',
+        PieceType::Pawn | PieceType::King => panic!("pawns can't promote to a pawn or king"),
+    }
+}
+
+/// The inverse of `promotion_uci_char`: the promotion piece denoted by a UCI move's trailing
+/// letter (e.g. the `q` in `e7e8q`).
+pub(crate) fn promotion_piece_from_uci_char(c: char) -> Option<PieceType> {
+    match c.to_ascii_lowercase() {
+        'n' => Some(PieceType::Knight),
+        'b' => Some(PieceType::Bishop),
+        'r' => Some(PieceType::Rook),
+        'q' => Some(PieceType::Queen),
+        _ => None,
+    }
+}
+
+/// Whether `chess_move` is the move a caller meant by giving just a destination square and
+/// (for pawns reaching the back rank) a promotion piece. Shared by `ChessMove::from_uci` and
+/// `Game::make_move`.
+pub(crate) fn move_matches(
+    chess_move: &ChessMove,
+    to: ChessIndex,
+    promotion: Option<PieceType>,
+) -> bool {
+    match (chess_move, promotion) {
+        (ChessMove::Regular(regular_move), None) => regular_move.to_idx() == to,
+        (ChessMove::Castle(castle_move), None) => castle_move.king_to() == to,
+        (ChessMove::EnPassant(en_passant_move), None) => en_passant_move.to_idx() == to,
+        (ChessMove::Promotion(promotion_move), Some(promotion_piece)) => {
+            promotion_move.to_idx() == to && promotion_move.promotion_piece() == promotion_piece
+        }
+        _ => false,
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseUciError {
+    WrongLength(usize),
+    InvalidSquare(String),
+    InvalidPromotionPiece(char),
+    NoSuchMove,
+}
+
+impl Display for ParseUciError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let output = match self {
+            ParseUciError::WrongLength(len) => format!(
+                "UCI moves are 4 or 5 characters long (e.g. 'e2e4', 'e7e8q'), found {}",
+                len
+            ),
+            ParseUciError::InvalidSquare(square) => format!("invalid square: '{}'", square),
+            ParseUciError::InvalidPromotionPiece(c) => {
+                format!("invalid promotion piece: '{}'", c)
+            }
+            ParseUciError::NoSuchMove => format!("that move isn't legal in this position"),
+        };
+
+        write!(f, "{}", output)
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
@@ -128,3 +495,20 @@ impl EnPassantMove {
         self.2
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::*;
+
+    #[test]
+    fn test_to_uci() {
+        assert_eq!(ChessMove::regular(E2, E4).to_uci(), "e2e4");
+        assert_eq!(
+            ChessMove::promotion(E7, E8, PieceType::Queen).to_uci(),
+            "e7e8q"
+        );
+        assert_eq!(ChessMove::castle(E1, G1, H1, F1).to_uci(), "e1g1");
+        assert_eq!(ChessMove::en_passant(E5, D6, D5).to_uci(), "e5d6");
+    }
+}
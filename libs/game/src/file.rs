@@ -5,7 +5,7 @@ use std::{
 };
 
 /// A chess file (vertical line)
-#[derive(Debug, Copy, PartialEq, Clone, Eq)]
+#[derive(Debug, Copy, PartialEq, Clone, Eq, serde::Serialize, serde::Deserialize)]
 pub enum File {
     A,
     B,
@@ -17,6 +17,16 @@ pub enum File {
     H,
 }
 
+impl File {
+    /// This file's 8 squares as a 64-bit mask, one bit per square in the same `a1` = bit 0,
+    /// `h8` = bit 63 ordering `Bitboard` and `ChessIndex`'s `u64` conversion use — e.g.
+    /// `File::A` sets bits 0, 8, 16, ..., 56. Delegates to `bitboard::file_mask` so there's a
+    /// single source of truth for the bit layout.
+    pub fn mask(&self) -> u64 {
+        crate::file_mask(*self).bits()
+    }
+}
+
 impl Add<u8> for File {
     type Output = Option<File>;
 
@@ -221,4 +231,22 @@ mod tests {
         assert_eq!(File::A - 1, None);
         assert_eq!(File::B - 1, Some(File::A));
     }
+
+    #[test]
+    fn test_file_mask_and_rank_mask_intersect_in_exactly_one_bit() {
+        use crate::Rank;
+
+        for file in FileIter::start_at(File::A) {
+            for rank in crate::RankIter::start_at(Rank::First) {
+                let intersection = file.mask() & rank.mask();
+                assert_eq!(
+                    intersection.count_ones(),
+                    1,
+                    "{:?}/{:?} mask intersection should be exactly one bit",
+                    file,
+                    rank
+                );
+            }
+        }
+    }
 }
@@ -1,23 +1,31 @@
 #![allow(dead_code)]
 
+pub mod ai;
+mod bitboard;
 mod chess_board;
 mod chess_index;
 mod chess_move;
 mod consts;
 mod file;
+mod messages;
 mod piece;
 mod rank;
 mod square;
+mod uci;
+mod zobrist;
 
-pub use chess_board::ChessBoard;
+pub use bitboard::*;
+pub use chess_board::{ChessBoard, FenError, PieceSquareTables, UndoInfo};
 pub use chess_index::*;
 pub use chess_move::*;
 pub use file::{File, FileIter};
+pub use messages::ChessMessage;
 pub use piece::*;
 pub use rank::{Rank, RankIter};
+pub use uci::{TimeControl, UciEngine, UciEngineOptions, UciError, UciStrategy};
 
 use consts::*;
-use std::{convert::TryFrom, fmt::Display};
+use std::{collections::HashMap, convert::TryFrom, fmt::Display, str::FromStr};
 
 #[derive(PartialEq, Clone, Copy, Debug, Eq)]
 pub enum Color {
@@ -45,6 +53,26 @@ impl Display for Color {
     }
 }
 
+/// Which side still has the right to castle kingside/queenside.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CastlingRights {
+    pub white_kingside: bool,
+    pub white_queenside: bool,
+    pub black_kingside: bool,
+    pub black_queenside: bool,
+}
+
+impl Default for CastlingRights {
+    fn default() -> Self {
+        Self {
+            white_kingside: true,
+            white_queenside: true,
+            black_kingside: true,
+            black_queenside: true,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Game {
     board: ChessBoard,
@@ -52,13 +80,266 @@ pub struct Game {
     black_king: ChessIndex,
     white_taken: Vec<Piece>,
     black_taken: Vec<Piece>,
-    history: Vec<ChessBoard>,
+    undo_stack: Vec<Undo>,
+    /// Moves undone by `unmake_move` but not yet overwritten by a new `execute_move`, so `redo`
+    /// can replay them. Cleared whenever `execute_move` plays a genuinely new move.
+    redo_stack: Vec<ChessMove>,
+    /// Every move played so far, in order, mirroring `undo_stack` — `history()` exposes this as
+    /// a plain slice of `ChessMove` instead of making callers dig `chess_move` out of `Undo`.
+    move_history: Vec<ChessMove>,
+    side_to_move: Color,
+    castling_rights: CastlingRights,
+    en_passant_target: Option<ChessIndex>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+    zobrist_hash: u64,
+    /// Occurrence count of every Zobrist hash seen so far, used by `is_draw_by_repetition`.
+    position_counts: HashMap<u64, u8>,
+}
+
+/// What `execute_move` pushes onto the undo stack so `unmake_move` can reverse a move in place,
+/// without cloning the board. Everything needed to undo the move itself comes from `chess_move`
+/// plus whatever it captured; the remaining fields are the state `execute_move` recomputes on
+/// every call and that can't otherwise be derived by looking at the board alone.
+#[derive(Debug, Clone)]
+struct Undo {
+    chess_move: ChessMove,
+    /// The piece captured by this move (if any) and the square it was captured on — for a
+    /// regular capture or promotion-with-capture that's the move's `to` square, for en passant
+    /// it's the taken pawn's square (which differs from `to`).
+    captured: Option<(ChessIndex, Piece)>,
+    /// For a promotion move, the pawn that was consumed to produce the promoted piece, so
+    /// `unmake_move` can put the exact same pawn (with its move history intact) back.
+    promoted_pawn: Option<Piece>,
+    zobrist_hash: u64,
+    en_passant_target: Option<ChessIndex>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+    castling_rights: CastlingRights,
 }
 
 impl Game {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Parses a full FEN string (piece placement, side to move, castling rights, en-passant
+    /// target, halfmove clock, fullmove number) into a `Game`.
+    pub fn from_fen(fen: &str) -> Result<Self, FenError> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        if fields.len() != 6 {
+            return Err(FenError::InvalidField(format!(
+                "expected 6 fields in FEN, found {}",
+                fields.len()
+            )));
+        }
+
+        let board = ChessBoard::from_str(fields[0])?;
+
+        let side_to_move = match fields[1] {
+            "w" => Color::White,
+            "b" => Color::Black,
+            other => return Err(FenError::InvalidField(other.to_string())),
+        };
+
+        let castling_rights = CastlingRights {
+            white_kingside: fields[2].contains('K'),
+            white_queenside: fields[2].contains('Q'),
+            black_kingside: fields[2].contains('k'),
+            black_queenside: fields[2].contains('q'),
+        };
+
+        let en_passant_target = match fields[3] {
+            "-" => None,
+            square => Some(
+                ChessIndex::from_str(square)
+                    .map_err(|_| FenError::InvalidField(square.to_string()))?,
+            ),
+        };
+
+        let halfmove_clock = fields[4]
+            .parse()
+            .map_err(|_| FenError::InvalidField(fields[4].to_string()))?;
+        let fullmove_number = fields[5]
+            .parse()
+            .map_err(|_| FenError::InvalidField(fields[5].to_string()))?;
+
+        let (white_king, black_king) =
+            validate_position(&board, castling_rights, en_passant_target)?;
+
+        let zobrist_hash =
+            compute_zobrist_hash(&board, side_to_move, castling_rights, en_passant_target);
+        let mut position_counts = HashMap::new();
+        position_counts.insert(zobrist_hash, 1);
+
+        let mut game = Self {
+            board,
+            white_king,
+            black_king,
+            white_taken: Vec::new(),
+            black_taken: Vec::new(),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            move_history: Vec::new(),
+            side_to_move,
+            castling_rights,
+            en_passant_target,
+            halfmove_clock,
+            fullmove_number,
+            zobrist_hash,
+            position_counts,
+        };
+
+        // Reconstruct enough pawn history for `valid_pawn_moves_from`'s en-passant check to
+        // recognize the pawn that just made the double step implied by `en_passant_target`.
+        if let Some(ep_target) = en_passant_target {
+            let pawn_rank_offset: i32 = match ep_target.rank() {
+                Rank::Third => 1,
+                Rank::Sixth => -1,
+                _ => 0,
+            };
+            let ep_file = u8::from(&ep_target.file()) as i32;
+            let ep_rank = i32::from(&ep_target.rank());
+            if let (Ok(pawn_index), Ok(start_index)) = (
+                ChessIndex::try_from((ep_file, ep_rank + pawn_rank_offset)),
+                ChessIndex::try_from((ep_file, ep_rank - pawn_rank_offset)),
+            ) {
+                if let Some(pawn) = game.board[pawn_index].piece_mut() {
+                    pawn.set_previous_index(start_index);
+                }
+            }
+        }
+
+        Ok(game)
+    }
+
+    /// The current position's Zobrist hash, incrementally maintained by `execute_move`.
+    pub fn position_hash(&self) -> u64 {
+        self.zobrist_hash
+    }
+
+    /// Whether the current position has occurred three or more times, making a draw claimable.
+    pub fn is_draw_by_repetition(&self) -> bool {
+        self.position_counts
+            .get(&self.zobrist_hash)
+            .copied()
+            .unwrap_or(0)
+            >= 3
+    }
+
+    /// Whether fifty full moves (a hundred halfmoves) have passed since the last pawn move or
+    /// capture, making a draw claimable.
+    pub fn is_draw_by_fifty_moves(&self) -> bool {
+        self.halfmove_clock >= 100
+    }
+
+    /// Whether neither side has enough material left to deliver checkmate: king vs. king, king
+    /// vs. king and a single minor piece, or king and bishop vs. king and bishop with both
+    /// bishops on the same color square.
+    pub fn is_draw_by_insufficient_material(&self) -> bool {
+        let mut white_minors = Vec::new();
+        let mut black_minors = Vec::new();
+
+        for rank in RankIter::start_at(Rank::First) {
+            for file in FileIter::start_at(File::A) {
+                let idx = ChessIndex::new(file, rank);
+                let piece = match self.board.piece_at(idx) {
+                    Some(piece) => piece,
+                    None => continue,
+                };
+
+                match piece.piece_type() {
+                    PieceType::King => continue,
+                    PieceType::Knight | PieceType::Bishop => match piece.color() {
+                        Color::White => white_minors.push((piece.piece_type(), idx)),
+                        Color::Black => black_minors.push((piece.piece_type(), idx)),
+                    },
+                    // a pawn, rook, or queen anywhere on the board is always enough material.
+                    PieceType::Pawn | PieceType::Rook | PieceType::Queen => return false,
+                }
+            }
+        }
+
+        match (white_minors.as_slice(), black_minors.as_slice()) {
+            ([], []) => true,
+            ([(PieceType::Knight | PieceType::Bishop, _)], []) => true,
+            ([], [(PieceType::Knight | PieceType::Bishop, _)]) => true,
+            ([(PieceType::Bishop, white_bishop)], [(PieceType::Bishop, black_bishop)]) => {
+                square_color(*white_bishop) == square_color(*black_bishop)
+            }
+            _ => false,
+        }
+    }
+
+    /// How the game has ended, or `None` if it's still ongoing.
+    ///
+    /// Checkmate and stalemate are derived from `legal_moves` for whichever side is on the
+    /// move; the remaining draw conditions pair with `is_draw_by_insufficient_material`,
+    /// `is_draw_by_fifty_moves`, and `is_draw_by_repetition`.
+    pub fn outcome(&self) -> Option<Outcome> {
+        if self.legal_moves().is_empty() {
+            return Some(if self.is_king_checked(self.side_to_move) {
+                Outcome::Decisive {
+                    winner: self.side_to_move.opponent(),
+                }
+            } else {
+                Outcome::Draw
+            });
+        }
+
+        if self.is_draw_by_insufficient_material()
+            || self.is_draw_by_fifty_moves()
+            || self.is_draw_by_repetition()
+        {
+            return Some(Outcome::Draw);
+        }
+
+        None
+    }
+
+    /// Serializes this position as a full FEN string.
+    pub fn to_fen(&self) -> String {
+        let castling = {
+            let mut s = String::new();
+            if self.castling_rights.white_kingside {
+                s.push('K');
+            }
+            if self.castling_rights.white_queenside {
+                s.push('Q');
+            }
+            if self.castling_rights.black_kingside {
+                s.push('k');
+            }
+            if self.castling_rights.black_queenside {
+                s.push('q');
+            }
+            if s.is_empty() {
+                s.push('-');
+            }
+            s
+        };
+
+        let side_to_move = match self.side_to_move {
+            Color::White => "w",
+            Color::Black => "b",
+        };
+
+        let en_passant = match self.en_passant_target {
+            Some(idx) => idx.to_string(),
+            None => "-".to_string(),
+        };
+
+        format!(
+            "{} {} {} {} {} {}",
+            self.board.to_fen(),
+            side_to_move,
+            castling,
+            en_passant,
+            self.halfmove_clock,
+            self.fullmove_number
+        )
+    }
+
     /// Check if a square is in check
     pub fn is_checked(&self, index: ChessIndex, color: Color) -> bool {
         if let Some(_knight_idx) = self.is_checked_by_knight(index, color) {
@@ -435,9 +716,133 @@ impl Game {
         None
     }
 
-    fn undo_last_move(&mut self) {
-        if !self.history.is_empty() {
-            self.board = self.history.pop().unwrap();
+    /// Pops the last `Undo` record pushed by `execute_move` and reverses its mutation in place —
+    /// the counterpart to `execute_move` that a search's make/unmake loop drives without ever
+    /// cloning the board.
+    pub fn unmake_move(&mut self) {
+        if let Some(undo) = self.undo_stack.pop() {
+            self.move_history.pop();
+            self.redo_stack.push(undo.chess_move);
+
+            if let Some(count) = self.position_counts.get_mut(&self.zobrist_hash) {
+                *count -= 1;
+                if *count == 0 {
+                    self.position_counts.remove(&self.zobrist_hash);
+                }
+            }
+
+            self.zobrist_hash = undo.zobrist_hash;
+            self.en_passant_target = undo.en_passant_target;
+            self.halfmove_clock = undo.halfmove_clock;
+            self.fullmove_number = undo.fullmove_number;
+            self.castling_rights = undo.castling_rights;
+            self.side_to_move = self.side_to_move.opponent();
+
+            match undo.chess_move {
+                ChessMove::Regular(regular_move) => {
+                    self.unmake_regular_move(regular_move, undo.captured)
+                }
+                ChessMove::Castle(castle_move) => self.unmake_castle_move(castle_move),
+                ChessMove::Promotion(promotion_move) => self.unmake_promotion_move(
+                    promotion_move,
+                    undo.captured,
+                    undo.promoted_pawn
+                        .expect("promotion undo record must carry the original pawn"),
+                ),
+                ChessMove::EnPassant(en_passant_move) => self.unmake_en_passant_move(
+                    en_passant_move,
+                    undo.captured
+                        .expect("en passant undo record must carry the taken pawn"),
+                ),
+            }
+        }
+    }
+
+    fn unmake_regular_move(
+        &mut self,
+        regular_move: RegularMove,
+        captured: Option<(ChessIndex, Piece)>,
+    ) {
+        let from = regular_move.from_idx();
+        let to = regular_move.to_idx();
+
+        let mut piece = self.board[to]
+            .take_piece()
+            .expect("no piece on undone move's to square");
+        piece.pop_index_from_history();
+
+        if piece.is_king() {
+            match piece.color() {
+                Color::Black => self.black_king = from,
+                Color::White => self.white_king = from,
+            }
+        }
+
+        if let Some((square, captured_piece)) = &captured {
+            self.remove_last_taken_piece(piece.color());
+            self.board[*square].set_piece(captured_piece.clone());
+        }
+
+        self.board[from].set_piece(piece);
+    }
+
+    fn unmake_castle_move(&mut self, castle_move: CastleMove) {
+        let mut king = self.board[castle_move.king_to()]
+            .take_piece()
+            .expect("no king on undone castle's to square");
+        king.pop_index_from_history();
+        match king.color() {
+            Color::Black => self.black_king = castle_move.king_from(),
+            Color::White => self.white_king = castle_move.king_from(),
+        }
+        self.board[castle_move.king_from()].set_piece(king);
+
+        let mut rook = self.board[castle_move.rook_to()]
+            .take_piece()
+            .expect("no rook on undone castle's to square");
+        rook.pop_index_from_history();
+        self.board[castle_move.rook_from()].set_piece(rook);
+    }
+
+    fn unmake_promotion_move(
+        &mut self,
+        promotion_move: PromotionMove,
+        captured: Option<(ChessIndex, Piece)>,
+        original_pawn: Piece,
+    ) {
+        self.board[promotion_move.to_idx()]
+            .take_piece()
+            .expect("no promoted piece on undone move's to square");
+
+        if let Some((square, captured_piece)) = captured {
+            self.remove_last_taken_piece(original_pawn.color());
+            self.board[square].set_piece(captured_piece);
+        }
+
+        self.board[promotion_move.from_idx()].set_piece(original_pawn);
+    }
+
+    fn unmake_en_passant_move(
+        &mut self,
+        en_passant_move: EnPassantMove,
+        captured: (ChessIndex, Piece),
+    ) {
+        let mut pawn = self.board[en_passant_move.to_idx()]
+            .take_piece()
+            .expect("no pawn on undone en passant's to square");
+        pawn.pop_index_from_history();
+
+        let (square, captured_piece) = captured;
+        self.remove_last_taken_piece(pawn.color());
+        self.board[square].set_piece(captured_piece);
+
+        self.board[en_passant_move.from_idx()].set_piece(pawn);
+    }
+
+    fn remove_last_taken_piece(&mut self, capturing_color: Color) -> Option<Piece> {
+        match capturing_color {
+            Color::Black => self.black_taken.pop(),
+            Color::White => self.white_taken.pop(),
         }
     }
 
@@ -467,7 +872,7 @@ impl Game {
             } else {
                 actual_valid_moves.push(valid_move);
             }
-            clone.undo_last_move();
+            clone.unmake_move();
         }
 
         actual_valid_moves
@@ -571,92 +976,21 @@ impl Game {
     }
 
     fn valid_king_moves_from(&self, from_index: ChessIndex, piece_color: Color) -> Vec<ChessMove> {
-        let mut moves: Vec<ChessMove> = Vec::new();
-
-        // increasing file
-        if let Some(file) = from_index.file() + 1 {
-            let to_index = ChessIndex::new(file, from_index.rank());
-            match self.board[to_index].piece() {
-                Some(p) if p.color() == piece_color => {}
-                _ => {
-                    moves.push(ChessMove::regular(from_index, to_index));
-                }
-            }
-        }
-
-        // decreasing file
-        if let Some(file) = from_index.file() - 1 {
-            let to_index = ChessIndex::new(file, from_index.rank());
-            match self.board[to_index].piece() {
-                Some(p) if p.color() == piece_color => {}
-                _ => {
-                    moves.push(ChessMove::regular(from_index, to_index));
-                }
-            }
-        }
-
-        // increasing rank
-        if let Some(rank) = from_index.rank() + 1 {
-            let to_index = ChessIndex::new(from_index.file(), rank);
-            match self.board[to_index].piece() {
-                Some(p) if p.color() == piece_color => {}
-                _ => {
-                    moves.push(ChessMove::regular(from_index, to_index));
-                }
-            }
-        }
-
-        // decreasing rank
-        if let Some(rank) = from_index.rank() - 1 {
-            let to_index = ChessIndex::new(from_index.file(), rank);
-            match self.board[to_index].piece() {
-                Some(p) if p.color() == piece_color => {}
-                _ => {
-                    moves.push(ChessMove::regular(from_index, to_index));
-                }
-            }
-        }
-
-        // increasing file, increasing rank
-        if let (Some(file), Some(rank)) = (from_index.file() + 1, from_index.rank() + 1) {
-            let to_index = ChessIndex::new(file, rank);
-            match self.board[to_index].piece() {
-                Some(p) if p.color() == piece_color => {}
-                _ => {
-                    moves.push(ChessMove::regular(from_index, to_index));
-                }
-            }
-        }
-
-        // increasing file, decreasing rank
-        if let (Some(file), Some(rank)) = (from_index.file() + 1, from_index.rank() - 1) {
-            let to_index = ChessIndex::new(file, rank);
-            match self.board[to_index].piece() {
-                Some(p) if p.color() == piece_color => {}
-                _ => {
-                    moves.push(ChessMove::regular(from_index, to_index));
-                }
-            }
-        }
-
-        // decreasing file, increasing rank
-        if let (Some(file), Some(rank)) = (from_index.file() - 1, from_index.rank() + 1) {
-            let to_index = ChessIndex::new(file, rank);
-            match self.board[to_index].piece() {
-                Some(p) if p.color() == piece_color => {}
-                _ => {
-                    moves.push(ChessMove::regular(from_index, to_index));
-                }
-            }
-        }
+        let own_occupancy = self.board.occupancy_for(piece_color);
+        let attacks = king_attacks(from_index) & !own_occupancy;
+        let mut moves: Vec<ChessMove> = attacks
+            .squares()
+            .map(|to_index| ChessMove::regular(from_index, to_index))
+            .collect();
 
-        // decreasing file, decreasing rank
-        if let (Some(file), Some(rank)) = (from_index.file() - 1, from_index.rank() - 1) {
-            let to_index = ChessIndex::new(file, rank);
-            match self.board[to_index].piece() {
-                Some(p) if p.color() == piece_color => {}
-                _ => {
-                    moves.push(ChessMove::regular(from_index, to_index));
+        let (king_home, kingside_rook_home, queenside_rook_home) = match piece_color {
+            Color::White => (E1, H1, A1),
+            Color::Black => (E8, H8, A8),
+        };
+        if from_index == king_home {
+            for rook_home in [kingside_rook_home, queenside_rook_home] {
+                if let Ok(castle_move) = self.can_castle(king_home, rook_home) {
+                    moves.push(ChessMove::Castle(castle_move));
                 }
             }
         }
@@ -687,33 +1021,12 @@ impl Game {
         from_index: ChessIndex,
         piece_color: Color,
     ) -> Vec<ChessMove> {
-        let mut moves = Vec::new();
-
-        let offsets = vec![
-            (2, 1),
-            (2, -1),
-            (-2, 1),
-            (-2, -1),
-            (1, 2),
-            (1, -2),
-            (-1, 2),
-            (-1, -2),
-        ];
-
-        for (file_offset, rank_offset) in offsets {
-            if let Ok(to_index) = ChessIndex::try_from((
-                u8::from(&from_index.file()) as i32 + file_offset,
-                u8::from(&from_index.rank()) as i32 + rank_offset,
-            )) {
-                match self.board[to_index].piece() {
-                    Some(p) if p.color() == piece_color => {}
-                    _ => {
-                        moves.push(ChessMove::regular(from_index, to_index));
-                    }
-                }
-            }
-        }
-        moves
+        let own_occupancy = self.board.occupancy_for(piece_color);
+        let attacks = knight_attacks(from_index) & !own_occupancy;
+        attacks
+            .squares()
+            .map(|to_index| ChessMove::regular(from_index, to_index))
+            .collect()
     }
 
     fn valid_bishop_moves_from(
@@ -757,17 +1070,279 @@ impl Game {
         }
     }
 
+    /// The piece (and its square) that `chess_move` is about to capture, read before the board
+    /// is mutated so `execute_move` can stash it on the undo stack.
+    fn captured_by(&self, chess_move: ChessMove) -> Option<(ChessIndex, Piece)> {
+        let square = match chess_move {
+            ChessMove::Regular(regular_move) => regular_move.to_idx(),
+            ChessMove::Promotion(promotion_move) => promotion_move.to_idx(),
+            ChessMove::EnPassant(en_passant_move) => en_passant_move.taken_pawn_idx(),
+            ChessMove::Castle(_) => return None,
+        };
+        self.board[square].piece().cloned().map(|piece| (square, piece))
+    }
+
+    /// Plays `chess_move`, pushing it onto `history()` and clearing any moves `redo` could have
+    /// replayed — playing a genuinely new move abandons whatever line was undone.
     pub fn execute_move(&mut self, chess_move: ChessMove) {
-        let prev = self.board.clone();
+        self.redo_stack.clear();
+        self.apply_move(chess_move);
+    }
+
+    /// Replays the most recently undone move, the counterpart to `unmake_move`. Returns `false`
+    /// if there's nothing left to redo.
+    pub fn redo(&mut self) -> bool {
+        match self.redo_stack.pop() {
+            Some(chess_move) => {
+                self.apply_move(chess_move);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Every move played so far, in order, reflecting `unmake_move`/`redo` calls.
+    pub fn history(&self) -> &[ChessMove] {
+        &self.move_history
+    }
+
+    fn apply_move(&mut self, chess_move: ChessMove) {
+        let captured = self.captured_by(chess_move);
+        let promoted_pawn = match chess_move {
+            ChessMove::Promotion(promotion_move) => self.board[promotion_move.from_idx()]
+                .piece()
+                .cloned(),
+            _ => None,
+        };
+
+        let undo = Undo {
+            chess_move,
+            captured,
+            promoted_pawn,
+            zobrist_hash: self.zobrist_hash,
+            en_passant_target: self.en_passant_target,
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+            castling_rights: self.castling_rights,
+        };
+
+        let resets_halfmove_clock = self.move_resets_halfmove_clock(chess_move);
+        let next_en_passant_target = self.next_en_passant_target(chess_move);
+        self.update_castling_rights(chess_move);
+
+        // Each arm toggles the Zobrist hash for the squares it's about to touch *before*
+        // mutating the board, since the toggle needs to see what's currently standing there.
+        match chess_move {
+            ChessMove::Regular(regular_move) => {
+                self.toggle_hash_for_regular_move(regular_move);
+                self.execute_regular_move(regular_move);
+            }
+            ChessMove::Castle(castle_move) => {
+                self.toggle_hash_for_castle_move(castle_move);
+                self.execute_castle_move(castle_move);
+            }
+            ChessMove::Promotion(promotion_move) => {
+                self.toggle_hash_for_promotion_move(promotion_move);
+                self.execute_promotion_move(promotion_move);
+            }
+            ChessMove::EnPassant(en_passant_move) => {
+                self.toggle_hash_for_en_passant_move(en_passant_move);
+                self.execute_en_passant_move(en_passant_move);
+            }
+        }
+
+        if let Some(file) = self.en_passant_target.map(|idx| idx.file()) {
+            self.zobrist_hash ^= zobrist::en_passant_file_key(file);
+        }
+        self.en_passant_target = next_en_passant_target;
+        if let Some(file) = self.en_passant_target.map(|idx| idx.file()) {
+            self.zobrist_hash ^= zobrist::en_passant_file_key(file);
+        }
+
+        self.zobrist_hash ^= zobrist::side_to_move_key();
+        if self.side_to_move == Color::Black {
+            self.fullmove_number += 1;
+        }
+        self.side_to_move = self.side_to_move.opponent();
+
+        self.halfmove_clock = if resets_halfmove_clock {
+            0
+        } else {
+            self.halfmove_clock + 1
+        };
+
+        *self.position_counts.entry(self.zobrist_hash).or_insert(0) += 1;
+
+        self.move_history.push(chess_move);
+        self.undo_stack.push(undo);
+    }
+
+    fn toggle_hash_for_regular_move(&mut self, regular_move: RegularMove) {
+        let from = regular_move.from_idx();
+        let to = regular_move.to_idx();
+
+        let moving = self.board[from]
+            .piece()
+            .expect("no piece on regular move's from square");
+        self.zobrist_hash ^= zobrist::piece_key(moving.piece_type(), moving.color(), from);
+        if let Some(captured) = self.board[to].piece() {
+            self.zobrist_hash ^= zobrist::piece_key(captured.piece_type(), captured.color(), to);
+        }
+        self.zobrist_hash ^= zobrist::piece_key(moving.piece_type(), moving.color(), to);
+    }
+
+    fn toggle_hash_for_castle_move(&mut self, castle_move: CastleMove) {
+        let king = self.board[castle_move.king_from()]
+            .piece()
+            .expect("no king on castle move's king_from square");
+        let rook = self.board[castle_move.rook_from()]
+            .piece()
+            .expect("no rook on castle move's rook_from square");
+
+        self.zobrist_hash ^= zobrist::piece_key(
+            king.piece_type(),
+            king.color(),
+            castle_move.king_from(),
+        );
+        self.zobrist_hash ^= zobrist::piece_key(
+            rook.piece_type(),
+            rook.color(),
+            castle_move.rook_from(),
+        );
+        self.zobrist_hash ^=
+            zobrist::piece_key(king.piece_type(), king.color(), castle_move.king_to());
+        self.zobrist_hash ^=
+            zobrist::piece_key(rook.piece_type(), rook.color(), castle_move.rook_to());
+    }
+
+    fn toggle_hash_for_promotion_move(&mut self, promotion_move: PromotionMove) {
+        let from = promotion_move.from_idx();
+        let to = promotion_move.to_idx();
+
+        let pawn_color = self.board[from]
+            .piece()
+            .expect("no pawn on promotion move's from square")
+            .color();
+        self.zobrist_hash ^= zobrist::piece_key(PieceType::Pawn, pawn_color, from);
+        if let Some(captured) = self.board[to].piece() {
+            self.zobrist_hash ^= zobrist::piece_key(captured.piece_type(), captured.color(), to);
+        }
+        self.zobrist_hash ^=
+            zobrist::piece_key(promotion_move.promotion_piece(), pawn_color, to);
+    }
+
+    fn toggle_hash_for_en_passant_move(&mut self, en_passant_move: EnPassantMove) {
+        let from = en_passant_move.from_idx();
+        let to = en_passant_move.to_idx();
+        let taken_pawn_idx = en_passant_move.taken_pawn_idx();
+
+        let pawn_color = self.board[from]
+            .piece()
+            .expect("no pawn on en passant move's from square")
+            .color();
+        self.zobrist_hash ^= zobrist::piece_key(PieceType::Pawn, pawn_color, from);
+
+        let taken_pawn_color = self.board[taken_pawn_idx]
+            .piece()
+            .expect("no pawn to take en passant")
+            .color();
+        self.zobrist_hash ^= zobrist::piece_key(PieceType::Pawn, taken_pawn_color, taken_pawn_idx);
+
+        self.zobrist_hash ^= zobrist::piece_key(PieceType::Pawn, pawn_color, to);
+    }
+
+    /// Clears whichever castling rights a move invalidates — a king or rook leaving its home
+    /// square, or a rook being captured on its home square — and keeps the Zobrist hash in sync
+    /// with the change.
+    fn update_castling_rights(&mut self, chess_move: ChessMove) {
+        let before = self.castling_rights;
+
+        match chess_move {
+            ChessMove::Regular(regular_move) => {
+                self.clear_castling_rights_for_square(regular_move.from_idx());
+                self.clear_castling_rights_for_square(regular_move.to_idx());
+            }
+            ChessMove::Castle(castle_move) => {
+                self.clear_castling_rights_for_square(castle_move.king_from());
+            }
+            ChessMove::Promotion(promotion_move) => {
+                self.clear_castling_rights_for_square(promotion_move.from_idx());
+                self.clear_castling_rights_for_square(promotion_move.to_idx());
+            }
+            ChessMove::EnPassant(_) => {}
+        }
+
+        if before.white_kingside != self.castling_rights.white_kingside {
+            self.zobrist_hash ^= zobrist::castling_key(0);
+        }
+        if before.white_queenside != self.castling_rights.white_queenside {
+            self.zobrist_hash ^= zobrist::castling_key(1);
+        }
+        if before.black_kingside != self.castling_rights.black_kingside {
+            self.zobrist_hash ^= zobrist::castling_key(2);
+        }
+        if before.black_queenside != self.castling_rights.black_queenside {
+            self.zobrist_hash ^= zobrist::castling_key(3);
+        }
+    }
+
+    fn clear_castling_rights_for_square(&mut self, index: ChessIndex) {
+        match index {
+            E1 => {
+                self.castling_rights.white_kingside = false;
+                self.castling_rights.white_queenside = false;
+            }
+            A1 => self.castling_rights.white_queenside = false,
+            H1 => self.castling_rights.white_kingside = false,
+            E8 => {
+                self.castling_rights.black_kingside = false;
+                self.castling_rights.black_queenside = false;
+            }
+            A8 => self.castling_rights.black_queenside = false,
+            H8 => self.castling_rights.black_kingside = false,
+            _ => {}
+        }
+    }
 
+    /// Pawn moves and captures irreversibly change the position, so they reset the clock that
+    /// `is_draw_by_fifty_moves` watches.
+    fn move_resets_halfmove_clock(&self, chess_move: ChessMove) -> bool {
         match chess_move {
-            ChessMove::Regular(regular_move) => self.execute_regular_move(regular_move),
-            ChessMove::Castle(castle_move) => self.execute_castle_move(castle_move),
-            ChessMove::Promotion(promotion_move) => self.execute_promotion_move(promotion_move),
-            ChessMove::EnPassant(en_passant_move) => self.execute_en_passant_move(en_passant_move),
+            ChessMove::Regular(regular_move) => {
+                let is_pawn_move = self.board[regular_move.from_idx()]
+                    .piece()
+                    .map(|p| p.is_pawn())
+                    .unwrap_or(false);
+                let is_capture = self.board[regular_move.to_idx()].piece().is_some();
+                is_pawn_move || is_capture
+            }
+            ChessMove::Castle(_) => false,
+            ChessMove::Promotion(_) | ChessMove::EnPassant(_) => true,
+        }
+    }
+
+    /// The square a pawn passed over this move, if it just made its initial two-step advance —
+    /// this becomes the next `en_passant_target`, or `None` if no such square applies.
+    fn next_en_passant_target(&self, chess_move: ChessMove) -> Option<ChessIndex> {
+        let regular_move = match chess_move {
+            ChessMove::Regular(regular_move) => regular_move,
+            _ => return None,
+        };
+
+        let from = regular_move.from_idx();
+        let to = regular_move.to_idx();
+
+        if !self.board[from].piece()?.is_pawn() {
+            return None;
+        }
+
+        let rank_diff = i32::from(&to.rank()) - i32::from(&from.rank());
+        if rank_diff != 2 && rank_diff != -2 {
+            return None;
         }
 
-        self.history.push(prev);
+        let passed_rank = Rank::try_from(i32::from(&from.rank()) + rank_diff / 2).ok()?;
+        Some(ChessIndex::new(from.file(), passed_rank))
     }
 
     fn execute_promotion_move(&mut self, promotion_move: PromotionMove) {
@@ -820,7 +1395,12 @@ impl Game {
             panic!();
         }
 
-        self.board.set_piece(castle_move.king_to(), king);
+        match king.color() {
+            Color::Black => self.black_king = castle_move.king_to(),
+            Color::White => self.white_king = castle_move.king_to(),
+        }
+
+        self.board.set_piece(castle_move.king_to(), king);
         self.board.set_piece(castle_move.rook_to(), rook);
     }
 
@@ -871,6 +1451,74 @@ impl Game {
         valid_moves_from.contains(&chess_move)
     }
 
+    /// All legal moves for whichever side is currently on the move.
+    pub fn legal_moves(&self) -> Vec<ChessMove> {
+        self.moves_for(self.side_to_move)
+    }
+
+    /// Plays a half-move for whichever side currently has the turn, validating it against
+    /// `valid_moves_from` first. `promotion` must be `Some` exactly when `from` to `to` is a
+    /// pawn reaching the back rank; any other combination matches no move and is rejected as
+    /// `IllegalMove`.
+    pub fn make_move(
+        &mut self,
+        from: ChessIndex,
+        to: ChessIndex,
+        promotion: Option<PieceType>,
+    ) -> Result<MoveOutcome, MovePieceError> {
+        let moving_color = self.board[from]
+            .piece()
+            .map(|piece| piece.color())
+            .ok_or(MovePieceError::NoPieceToMove(from))?;
+
+        if moving_color != self.side_to_move {
+            return Err(MovePieceError::WrongSideToMove);
+        }
+
+        let chess_move = self
+            .valid_moves_from(from)
+            .into_iter()
+            .find(|candidate| move_matches(candidate, to, promotion))
+            .ok_or(MovePieceError::IllegalMove)?;
+
+        self.execute_move(chess_move);
+
+        let side_to_respond = self.side_to_move;
+        let has_legal_moves = !self.legal_moves().is_empty();
+        let in_check = self.is_king_checked(side_to_respond);
+
+        Ok(match (has_legal_moves, in_check) {
+            (false, true) => MoveOutcome::Checkmate,
+            (false, false) => MoveOutcome::Stalemate,
+            (true, true) => MoveOutcome::Check,
+            (true, false) => MoveOutcome::Continues,
+        })
+    }
+
+    /// Parses long-algebraic UCI notation (`e2e4`, `e7e8q`) into a concrete `ChessMove`.
+    /// Delegates to `ChessMove::from_uci`, which resolves it against
+    /// `self.valid_moves_from(from)` the same way `make_move` resolves a bare destination
+    /// square. Doesn't check whose turn it is; callers that care can check `side_to_move`
+    /// themselves, same as with `valid_moves_from`.
+    pub fn parse_uci(&self, uci: &str) -> Result<ChessMove, ParseUciError> {
+        ChessMove::from_uci(uci, self)
+    }
+
+    /// Standard Algebraic Notation for `chess_move` (`Nf3`, `exd5`, `O-O`, `e8=Q+`, `Qxf7#`),
+    /// called with `self` still at the position `chess_move` is about to be played from.
+    /// Delegates to `ChessMove::to_san`, which works out disambiguation and the check/mate
+    /// suffix against `self`.
+    pub fn move_to_san(&self, chess_move: ChessMove) -> String {
+        chess_move.to_san(self)
+    }
+
+    /// Parses Standard Algebraic Notation (`Nf3`, `exd5`, `O-O`, `e8=Q+`, `Qxf7#`) into a
+    /// concrete `ChessMove`. Delegates to `ChessMove::from_san`, which resolves ambiguity
+    /// against `self.legal_moves()`.
+    pub fn parse_san(&self, san: &str) -> Result<ChessMove, ParseSanError> {
+        ChessMove::from_san(san, self)
+    }
+
     fn can_castle(
         &self,
         king_index: ChessIndex,
@@ -893,6 +1541,10 @@ impl Game {
 
         let color = king.color();
 
+        if !self.castling_right_for(king_index, rook_index, color) {
+            return Err(CanCastleError::NoCastlingRights);
+        }
+
         if king.has_made_move() {
             return Err(CanCastleError::PieceHasMadeMove(king_index));
         }
@@ -900,7 +1552,19 @@ impl Game {
             return Err(CanCastleError::PieceHasMadeMove(rook_index));
         }
 
-        // check that squares between the king and rook are empty and not in check
+        let (king_to, rook_to) = if king_index.file() < rook_index.file() {
+            (
+                ChessIndex::new((king_index.file() + 2).unwrap(), king_index.rank()),
+                ChessIndex::new((king_index.file() + 1).unwrap(), rook_index.rank()),
+            )
+        } else {
+            (
+                ChessIndex::new((king_index.file() - 2).unwrap(), king_index.rank()),
+                ChessIndex::new((king_index.file() - 1).unwrap(), rook_index.rank()),
+            )
+        };
+
+        // squares between the king and rook must be empty
         let indices_between = ChessIndex::indices_between(king_index, rook_index);
         debug_assert!(
             indices_between.len() == 4 || indices_between.len() == 5,
@@ -914,27 +1578,40 @@ impl Game {
                 // square between the king and rook is not empty
                 return Err(CanCastleError::PiecesBetween);
             }
-            if self.is_checked(index_in_between, color) {
-                return Err(CanCastleError::SquareInCheck(index_in_between));
-            }
         }
 
-        let (king_to, rook_to) = if king_index.file() < rook_index.file() {
-            (
-                ChessIndex::new((king_index.file() + 2).unwrap(), king_index.rank()),
-                ChessIndex::new((king_index.file() + 1).unwrap(), rook_index.rank()),
-            )
-        } else {
-            (
-                ChessIndex::new((king_index.file() - 2).unwrap(), king_index.rank()),
-                ChessIndex::new((king_index.file() - 1).unwrap(), rook_index.rank()),
-            )
-        };
+        // the king can't castle out of, through, or into check; on a queenside castle the
+        // rook's side of the gap (e.g. b1) isn't on the king's path, so it's fine for it to be
+        // attacked there
+        for index_on_kings_path in ChessIndex::indices_between(king_index, king_to) {
+            if self.is_checked(index_on_kings_path, color) {
+                return Err(CanCastleError::SquareInCheck(index_on_kings_path));
+            }
+        }
 
         Ok(CastleMove::new(king_index, king_to, rook_index, rook_to))
     }
 
-    /// Creates and consumes an iterator which steps by the given `file_step` and `rank_step` arguments until some other piece is reached
+    /// Which of the four `CastlingRights` flags governs castling between `king_index` and
+    /// `rook_index`, based on `color` and which side of the king the rook sits on.
+    fn castling_right_for(
+        &self,
+        king_index: ChessIndex,
+        rook_index: ChessIndex,
+        color: Color,
+    ) -> bool {
+        let kingside = king_index.file() < rook_index.file();
+        match (color, kingside) {
+            (Color::White, true) => self.castling_rights.white_kingside,
+            (Color::White, false) => self.castling_rights.white_queenside,
+            (Color::Black, true) => self.castling_rights.black_kingside,
+            (Color::Black, false) => self.castling_rights.black_queenside,
+        }
+    }
+
+    /// Slides from `start` along the single `(file_step, rank_step)` ray, stopping at (and
+    /// including, if it's an opponent's) the first occupied square. Backed by `ray_attacks`,
+    /// which walks the ray one square at a time until it reaches the first blocker.
     fn moves_to_opponents_piece(
         &self,
         start: ChessIndex,
@@ -942,44 +1619,328 @@ impl Game {
         rank_step: i32,
         color: Color,
     ) -> Vec<ChessMove> {
+        let own_occupancy = self.board.occupancy_for(color);
+        let occupancy = self.board.occupancy();
+        let attacks = ray_attacks(start, file_step, rank_step, occupancy) & !own_occupancy;
+        attacks
+            .squares()
+            .map(|idx| ChessMove::regular(start, idx))
+            .collect()
+    }
+
+    fn score(&self, player: Color) -> u8 {
+        match player {
+            Color::Black => self.black_taken.iter().map(|p| piece_value(p)).sum(),
+            Color::White => self.white_taken.iter().map(|p| piece_value(p)).sum(),
+        }
+    }
+
+    /// All valid moves for every piece belonging to `color`, in board order.
+    fn moves_for(&self, color: Color) -> Vec<ChessMove> {
         let mut moves = Vec::new();
-        for idx in (0..)
-            .map(|n| {
-                if let (Some(file), Some(rank)) = (
-                    File::try_from(i32::from(&start.file()) + n * file_step).ok(),
-                    Rank::try_from(i32::from(&start.rank()) + n * rank_step).ok(),
-                ) {
-                    let idx = ChessIndex::new(file, rank);
-                    Some(idx)
-                } else {
-                    None
+        for rank in RankIter::start_at(Rank::First) {
+            for file in FileIter::start_at(File::A) {
+                let idx = ChessIndex::new(file, rank);
+                if self.board[idx]
+                    .piece()
+                    .map(|p| p.color() == color)
+                    .unwrap_or(false)
+                {
+                    moves.append(&mut self.valid_moves_from(idx));
                 }
-            })
-            .take_while(|idx| idx.is_some())
-            .skip(1)
-        {
-            let idx = idx.expect("should always be some because we checked `idx.is_some()` above");
-            match self.board[idx].piece() {
-                Some(p) => {
-                    if p.color() == color.opponent() {
-                        moves.push(ChessMove::regular(start, idx));
-                    }
-                    break;
+            }
+        }
+        moves
+    }
+
+    /// Counts the leaf nodes reachable in exactly `depth` plies from the current position —
+    /// the standard correctness benchmark for a move generator (see `perft_divide` to break the
+    /// count down by root move).
+    pub fn perft(&mut self, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let mut nodes = 0;
+        for chess_move in self.moves_for(self.side_to_move) {
+            self.execute_move(chess_move);
+            nodes += self.perft(depth - 1);
+            self.unmake_move();
+        }
+        nodes
+    }
+
+    /// Like `perft`, but reports the node count contributed by each root move instead of just
+    /// the total — the usual way to narrow down which root move a perft discrepancy is hiding
+    /// under.
+    pub fn perft_divide(&mut self, depth: u32) -> Vec<(ChessMove, u64)> {
+        let mut counts = Vec::new();
+        for chess_move in self.moves_for(self.side_to_move) {
+            self.execute_move(chess_move);
+            let nodes = if depth == 0 { 1 } else { self.perft(depth - 1) };
+            self.unmake_move();
+            counts.push((chess_move, nodes));
+        }
+        counts
+    }
+
+    /// Picks the best move for the side to move by searching `depth` plies with negamax and
+    /// alpha-beta pruning, using the default `MaterialEvaluator` heuristic. Returns `None` if
+    /// the side to move has no legal moves (checkmate or stalemate).
+    pub fn best_move(&self, depth: u32) -> Option<ChessMove> {
+        self.best_move_with(&MaterialEvaluator, depth)
+    }
+
+    /// Like `best_move`, but scores leaves with `evaluator` instead of the built-in material
+    /// heuristic, using `execute_move`/`unmake_move` to walk the tree without cloning the board.
+    pub fn best_move_with<E: Evaluator>(&self, evaluator: &E, depth: u32) -> Option<ChessMove> {
+        let mut game = self.clone();
+        let color = game.side_to_move;
+        let mut alpha = -i32::MAX;
+        let beta = i32::MAX;
+
+        let mut best_move = None;
+        let mut best_score = -i32::MAX;
+        for chess_move in game.moves_for(color) {
+            game.execute_move(chess_move);
+            let score = -game.negamax(evaluator, depth.saturating_sub(1), -beta, -alpha, 1);
+            game.unmake_move();
+
+            if score > best_score {
+                best_score = score;
+                best_move = Some(chess_move);
+            }
+            alpha = alpha.max(score);
+        }
+
+        best_move
+    }
+
+    /// The recursive half of `best_move`: the score of the side to move, searching `depth` more
+    /// plies and pruning any branch once `alpha >= beta` (the opponent already has a reply that
+    /// makes this branch unreachable). `ply` counts half-moves since the root, purely so
+    /// `checkmate_score` can prefer a mate in fewer moves over an equally-winning, slower one.
+    fn negamax<E: Evaluator>(
+        &mut self,
+        evaluator: &E,
+        depth: u32,
+        mut alpha: i32,
+        beta: i32,
+        ply: u32,
+    ) -> i32 {
+        let side_to_move = self.side_to_move;
+        let moves = self.moves_for(side_to_move);
+
+        if moves.is_empty() {
+            return if self.is_king_checked(side_to_move) {
+                -checkmate_score(ply)
+            } else {
+                0
+            };
+        }
+
+        if depth == 0 {
+            return evaluator.evaluate(self, side_to_move);
+        }
+
+        let mut best_score = -i32::MAX;
+        for chess_move in moves {
+            self.execute_move(chess_move);
+            let score = -self.negamax(evaluator, depth - 1, -beta, -alpha, ply + 1);
+            self.unmake_move();
+
+            best_score = best_score.max(score);
+            alpha = alpha.max(best_score);
+            if alpha >= beta {
+                break;
+            }
+        }
+        best_score
+    }
+}
+
+/// A pluggable static position evaluation used by `Game::best_move_with`, scored from the
+/// perspective of `side_to_move` (positive means `side_to_move` is better) so `negamax` can
+/// always maximize. Implement this to swap in a different heuristic than `MaterialEvaluator`.
+pub trait Evaluator {
+    fn evaluate(&self, game: &Game, side_to_move: Color) -> i32;
+}
+
+/// The built-in `Evaluator`: material balance plus a flat centre-control bonus. Material uses
+/// the same per-piece values `piece_value` assigns for scoring captures; the centre bonus is a
+/// piece-square-table stand-in, not piece-specific.
+pub struct MaterialEvaluator;
+
+impl Evaluator for MaterialEvaluator {
+    fn evaluate(&self, game: &Game, side_to_move: Color) -> i32 {
+        let mut white_relative_score = 0;
+        for rank in RankIter::start_at(Rank::First) {
+            for file in FileIter::start_at(File::A) {
+                let idx = ChessIndex::new(file, rank);
+                if let Some(piece) = game.board[idx].piece() {
+                    let value = piece_value(piece) as i32 + center_bonus(idx);
+                    white_relative_score += match piece.color() {
+                        Color::White => value,
+                        Color::Black => -value,
+                    };
                 }
-                None => {
-                    moves.push(ChessMove::regular(start, idx));
+            }
+        }
+
+        match side_to_move {
+            Color::White => white_relative_score,
+            Color::Black => -white_relative_score,
+        }
+    }
+}
+
+/// Why a position (e.g. one parsed from FEN) is illegal, checked by `validate_position` before
+/// a `Game` is ever built around it.
+#[derive(Debug, PartialEq)]
+pub enum InvalidError {
+    /// A pawn was found on rank 1 or 8, where no pawn can ever legally stand.
+    InvalidPawnPosition(ChessIndex),
+    /// The two kings stand on adjacent squares, which would mean both are in check.
+    NeighbouringKings,
+    /// `color` has no king.
+    MissingKing(Color),
+    /// `color` has more than one king.
+    MultipleKings(Color),
+    /// A castling right is set but the king and/or rook it depends on isn't on its home square.
+    InvalidCastlingRights,
+    /// The en-passant target isn't empty, isn't on rank 3/6, or has no enemy pawn standing in
+    /// front of it on rank 4/5.
+    InvalidEnPassant(ChessIndex),
+}
+
+impl Display for InvalidError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let output = match self {
+            InvalidError::InvalidPawnPosition(idx) => {
+                format!("pawns can't stand on rank 1 or 8, found one on {}", idx)
+            }
+            InvalidError::NeighbouringKings => {
+                format!("the two kings can't stand on adjacent squares")
+            }
+            InvalidError::MissingKing(color) => format!("{} has no king", color),
+            InvalidError::MultipleKings(color) => format!("{} has more than one king", color),
+            InvalidError::InvalidCastlingRights => format!(
+                "a castling right is set but the king and/or rook it depends on isn't on its home square"
+            ),
+            InvalidError::InvalidEnPassant(idx) => {
+                format!("{} isn't a valid en passant target square", idx)
+            }
+        };
+
+        write!(f, "{}", output)
+    }
+}
+
+/// Validates a position before a `Game` is built around it, returning the two kings' squares on
+/// success so callers (namely `Game::from_fen`) don't have to scan the board for them twice.
+fn validate_position(
+    board: &ChessBoard,
+    castling_rights: CastlingRights,
+    en_passant_target: Option<ChessIndex>,
+) -> Result<(ChessIndex, ChessIndex), InvalidError> {
+    let (white_king, black_king) = validate_pieces(board)?;
+    validate_castling_rights(board, castling_rights)?;
+    if let Some(idx) = en_passant_target {
+        validate_en_passant(board, idx)?;
+    }
+    Ok((white_king, black_king))
+}
+
+/// Walks every square once, rejecting pawns on rank 1/8 and making sure each color has exactly
+/// one king, and that the two kings don't stand adjacent to each other.
+fn validate_pieces(board: &ChessBoard) -> Result<(ChessIndex, ChessIndex), InvalidError> {
+    let mut white_king = None;
+    let mut black_king = None;
+
+    for rank in RankIter::start_at(Rank::First) {
+        for file in FileIter::start_at(File::A) {
+            let idx = ChessIndex::new(file, rank);
+            let piece = match board.piece_at(idx) {
+                Some(piece) => piece,
+                None => continue,
+            };
+
+            if piece.is_pawn() && (rank == Rank::First || rank == Rank::Eighth) {
+                return Err(InvalidError::InvalidPawnPosition(idx));
+            }
+
+            if piece.is_king() {
+                let slot = match piece.color() {
+                    Color::White => &mut white_king,
+                    Color::Black => &mut black_king,
+                };
+                if slot.is_some() {
+                    return Err(InvalidError::MultipleKings(piece.color()));
                 }
+                *slot = Some(idx);
             }
         }
+    }
 
-        moves
+    let white_king = white_king.ok_or(InvalidError::MissingKing(Color::White))?;
+    let black_king = black_king.ok_or(InvalidError::MissingKing(Color::Black))?;
+
+    let file_distance =
+        (i32::from(u8::from(&white_king.file())) - i32::from(u8::from(&black_king.file()))).abs();
+    let rank_distance = (i32::from(&white_king.rank()) - i32::from(&black_king.rank())).abs();
+    if file_distance <= 1 && rank_distance <= 1 {
+        return Err(InvalidError::NeighbouringKings);
     }
 
-    fn score(&self, player: Color) -> u8 {
-        match player {
-            Color::Black => self.black_taken.iter().map(|p| piece_value(p)).sum(),
-            Color::White => self.white_taken.iter().map(|p| piece_value(p)).sum(),
+    Ok((white_king, black_king))
+}
+
+/// Every set castling right needs its king and rook still standing on their home squares —
+/// otherwise the right is a leftover that no longer makes sense for this position.
+fn validate_castling_rights(
+    board: &ChessBoard,
+    castling_rights: CastlingRights,
+) -> Result<(), InvalidError> {
+    let rights = [
+        (castling_rights.white_kingside, E1, H1, Color::White),
+        (castling_rights.white_queenside, E1, A1, Color::White),
+        (castling_rights.black_kingside, E8, H8, Color::Black),
+        (castling_rights.black_queenside, E8, A8, Color::Black),
+    ];
+
+    for (has_right, king_square, rook_square, color) in rights {
+        if !has_right {
+            continue;
         }
+
+        let king_in_place =
+            matches!(board.piece_at(king_square), Some(p) if p.is_king() && p.color() == color);
+        let rook_in_place =
+            matches!(board.piece_at(rook_square), Some(p) if p.is_rook() && p.color() == color);
+        if !king_in_place || !rook_in_place {
+            return Err(InvalidError::InvalidCastlingRights);
+        }
+    }
+
+    Ok(())
+}
+
+/// An en-passant target must be empty, sit on rank 3 or 6, and have the enemy pawn that just
+/// double-stepped past it standing directly "in front" of it on rank 4 or 5.
+fn validate_en_passant(board: &ChessBoard, idx: ChessIndex) -> Result<(), InvalidError> {
+    if board.piece_at(idx).is_some() {
+        return Err(InvalidError::InvalidEnPassant(idx));
+    }
+
+    let (pawn_rank, pawn_color) = match idx.rank() {
+        Rank::Third => (Rank::Fourth, Color::White),
+        Rank::Sixth => (Rank::Fifth, Color::Black),
+        _ => return Err(InvalidError::InvalidEnPassant(idx)),
+    };
+
+    match board.piece_at(ChessIndex::new(idx.file(), pawn_rank)) {
+        Some(p) if p.is_pawn() && p.color() == pawn_color => Ok(()),
+        _ => Err(InvalidError::InvalidEnPassant(idx)),
     }
 }
 
@@ -989,6 +1950,7 @@ enum CanCastleError {
     SquareInCheck(ChessIndex),
     PiecesBetween,
     PieceHasMadeMove(ChessIndex),
+    NoCastlingRights,
 }
 
 impl Display for CanCastleError {
@@ -1007,6 +1969,9 @@ impl Display for CanCastleError {
                 "can't castle because the piece at {} has already moved",
                 idx
             ),
+            CanCastleError::NoCastlingRights => {
+                format!("can't castle because that side has already lost its castling rights")
+            }
         };
 
         write!(f, "{}", output)
@@ -1017,6 +1982,8 @@ impl Display for CanCastleError {
 pub enum MovePieceError {
     NoPieceToMove(ChessIndex),
     OwnPieceAtTarget,
+    WrongSideToMove,
+    IllegalMove,
 }
 
 impl Display for MovePieceError {
@@ -1024,12 +1991,79 @@ impl Display for MovePieceError {
         let output = match self {
             MovePieceError::NoPieceToMove(index) => format!("no piece at {}", index),
             MovePieceError::OwnPieceAtTarget => format!("can't move to a square you occupy"),
+            MovePieceError::WrongSideToMove => {
+                format!("can't move that piece, it isn't that color's turn")
+            }
+            MovePieceError::IllegalMove => format!("that move isn't legal in this position"),
         };
 
         write!(f, "{}", output)
     }
 }
 
+/// The from-scratch Zobrist hash of a position, used to seed `Game::zobrist_hash` on
+/// construction; every move afterwards updates it incrementally instead of recomputing it.
+fn compute_zobrist_hash(
+    board: &ChessBoard,
+    side_to_move: Color,
+    castling_rights: CastlingRights,
+    en_passant_target: Option<ChessIndex>,
+) -> u64 {
+    let mut hash = board.zobrist_hash();
+
+    if side_to_move == Color::Black {
+        hash ^= zobrist::side_to_move_key();
+    }
+
+    if castling_rights.white_kingside {
+        hash ^= zobrist::castling_key(0);
+    }
+    if castling_rights.white_queenside {
+        hash ^= zobrist::castling_key(1);
+    }
+    if castling_rights.black_kingside {
+        hash ^= zobrist::castling_key(2);
+    }
+    if castling_rights.black_queenside {
+        hash ^= zobrist::castling_key(3);
+    }
+
+    if let Some(ep_target) = en_passant_target {
+        hash ^= zobrist::en_passant_file_key(ep_target.file());
+    }
+
+    hash
+}
+
+/// What happened as a result of a `Game::make_move` call.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MoveOutcome {
+    /// The move was played and the game continues with no one in check.
+    Continues,
+    /// The move was played and puts the opponent in check.
+    Check,
+    /// The move was played and the opponent has no legal moves while in check.
+    Checkmate,
+    /// The move was played and the opponent has no legal moves but is not in check.
+    Stalemate,
+}
+
+/// How a game has ended, as reported by `Game::outcome`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Outcome {
+    /// `winner` delivered checkmate.
+    Decisive { winner: Color },
+    /// The game is drawn, by stalemate, insufficient material, the fifty-move rule, or
+    /// threefold repetition.
+    Draw,
+}
+
+/// Whether `idx` is a light or dark square, used by `is_draw_by_insufficient_material` to tell
+/// same-colored bishops apart from opposite-colored ones.
+fn square_color(idx: ChessIndex) -> bool {
+    (u8::from(&idx.file()) + u8::from(&idx.rank())) % 2 == 0
+}
+
 fn piece_value(p: &Piece) -> u8 {
     match p.piece_type() {
         PieceType::Pawn => 1,
@@ -1041,55 +2075,56 @@ fn piece_value(p: &Piece) -> u8 {
     }
 }
 
+/// How bad it is, from `negamax`'s perspective, to be checkmated `ply` half-moves from the
+/// search root: a large constant so it dwarfs any material/positional score, minus `ply` so a
+/// mate found sooner (smaller `ply`) is scored as more decisive than an equally forced one found
+/// deeper in the tree — otherwise the search would have no reason to prefer the faster mate.
+fn checkmate_score(ply: u32) -> i32 {
+    1_000_000 - ply as i32
+}
+
+/// A small piece-square-table stand-in: squares closer to the centre (d4/d5/e4/e5) are worth
+/// more, tapering to nothing on the rim, regardless of which piece occupies them.
+fn center_bonus(idx: ChessIndex) -> i32 {
+    let file_distance = (2 * i32::from(u8::from(&idx.file())) - 9).abs();
+    let rank_distance = (2 * i32::from(&idx.rank()) - 9).abs();
+    match file_distance.max(rank_distance) {
+        1 => 3,
+        3 => 2,
+        5 => 1,
+        _ => 0,
+    }
+}
+
 impl Default for Game {
     fn default() -> Self {
         use crate::Color::*;
 
-        let mut board = ChessBoard::default();
-
-        board.set_piece(A1, Piece::rook(White));
-        board.set_piece(B1, Piece::knight(White));
-        board.set_piece(C1, Piece::bishop(White));
-        board.set_piece(D1, Piece::queen(White));
-        board.set_piece(E1, Piece::king(White));
-        board.set_piece(F1, Piece::bishop(White));
-        board.set_piece(G1, Piece::knight(White));
-        board.set_piece(H1, Piece::rook(White));
-
-        board.set_piece(A2, Piece::pawn(White));
-        board.set_piece(B2, Piece::pawn(White));
-        board.set_piece(C2, Piece::pawn(White));
-        board.set_piece(D2, Piece::pawn(White));
-        board.set_piece(E2, Piece::pawn(White));
-        board.set_piece(F2, Piece::pawn(White));
-        board.set_piece(G2, Piece::pawn(White));
-        board.set_piece(H2, Piece::pawn(White));
-
-        board.set_piece(A7, Piece::pawn(Black));
-        board.set_piece(B7, Piece::pawn(Black));
-        board.set_piece(C7, Piece::pawn(Black));
-        board.set_piece(D7, Piece::pawn(Black));
-        board.set_piece(E7, Piece::pawn(Black));
-        board.set_piece(F7, Piece::pawn(Black));
-        board.set_piece(G7, Piece::pawn(Black));
-        board.set_piece(H7, Piece::pawn(Black));
-
-        board.set_piece(A8, Piece::rook(Black));
-        board.set_piece(B8, Piece::knight(Black));
-        board.set_piece(C8, Piece::bishop(Black));
-        board.set_piece(D8, Piece::queen(Black));
-        board.set_piece(E8, Piece::king(Black));
-        board.set_piece(F8, Piece::bishop(Black));
-        board.set_piece(G8, Piece::knight(Black));
-        board.set_piece(H8, Piece::rook(Black));
+        let board = ChessBoard::starting_position();
+        let (white_king, black_king) =
+            validate_pieces(&board).expect("starting position always has exactly one king per side");
+
+        let castling_rights = CastlingRights::default();
+        let zobrist_hash = compute_zobrist_hash(&board, White, castling_rights, None);
+        let mut position_counts = HashMap::new();
+        position_counts.insert(zobrist_hash, 1);
 
         Self {
             board,
-            white_king: E1,
-            black_king: E7,
+            white_king,
+            black_king,
             white_taken: Vec::new(),
             black_taken: Vec::new(),
-            history: Vec::new(),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            move_history: Vec::new(),
+            side_to_move: White,
+            castling_rights,
+            en_passant_target: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            zobrist_hash,
+            position_counts,
         }
     }
 }
@@ -1117,10 +2152,10 @@ mod tests {
                 ChessMove::regular(E4, F4),
                 ChessMove::regular(E4, G4),
                 ChessMove::regular(E4, H4),
-                ChessMove::regular(E4, D4),
-                ChessMove::regular(E4, C4),
-                ChessMove::regular(E4, B4),
                 ChessMove::regular(E4, A4),
+                ChessMove::regular(E4, B4),
+                ChessMove::regular(E4, C4),
+                ChessMove::regular(E4, D4),
             ]
         );
     }
@@ -1136,12 +2171,12 @@ mod tests {
         assert_eq!(
             game.valid_knight_moves_from(E4, White),
             vec![
-                ChessMove::regular(E4, G5),
+                ChessMove::regular(E4, C3),
                 ChessMove::regular(E4, G3),
                 ChessMove::regular(E4, C5),
-                ChessMove::regular(E4, C3),
-                ChessMove::regular(E4, F6),
+                ChessMove::regular(E4, G5),
                 ChessMove::regular(E4, D6),
+                ChessMove::regular(E4, F6),
             ]
         );
     }
@@ -1186,9 +2221,9 @@ mod tests {
                 ChessMove::regular(D4, F4),
                 ChessMove::regular(D4, G4),
                 ChessMove::regular(D4, H4),
-                ChessMove::regular(D4, C4),
-                ChessMove::regular(D4, B4),
                 ChessMove::regular(D4, A4),
+                ChessMove::regular(D4, B4),
+                ChessMove::regular(D4, C4),
                 ChessMove::regular(D4, E5),
                 ChessMove::regular(D4, F6),
                 ChessMove::regular(D4, G7),
@@ -1216,14 +2251,14 @@ mod tests {
         assert_eq!(
             game.valid_king_moves_from(E4, White),
             vec![
-                ChessMove::regular(E4, F4),
-                ChessMove::regular(E4, D4),
-                ChessMove::regular(E4, E5),
+                ChessMove::regular(E4, D3),
                 ChessMove::regular(E4, E3),
-                ChessMove::regular(E4, F5),
                 ChessMove::regular(E4, F3),
+                ChessMove::regular(E4, D4),
+                ChessMove::regular(E4, F4),
                 ChessMove::regular(E4, D5),
-                ChessMove::regular(E4, D3),
+                ChessMove::regular(E4, E5),
+                ChessMove::regular(E4, F5),
             ]
         );
 
@@ -1232,13 +2267,13 @@ mod tests {
         assert_eq!(
             game.valid_moves_from(E4),
             vec![
-                ChessMove::regular(E4, F4),
-                ChessMove::regular(E4, D4),
+                ChessMove::regular(E4, D3),
                 ChessMove::regular(E4, E3),
-                ChessMove::regular(E4, F5),
                 ChessMove::regular(E4, F3),
+                ChessMove::regular(E4, D4),
+                ChessMove::regular(E4, F4),
                 ChessMove::regular(E4, D5),
-                ChessMove::regular(E4, D3),
+                ChessMove::regular(E4, F5),
             ]
         );
     }
@@ -1432,7 +2467,7 @@ mod tests {
         // decreasing rank
         assert_eq!(
             game.moves_to_opponents_piece(E5, 0, -1, White),
-            vec![ChessMove::regular(E5, E4), ChessMove::regular(E5, E3),]
+            vec![ChessMove::regular(E5, E3), ChessMove::regular(E5, E4),]
         );
 
         // increasing file
@@ -1449,10 +2484,10 @@ mod tests {
         assert_eq!(
             game.moves_to_opponents_piece(E5, -1, 0, White),
             vec![
-                ChessMove::regular(E5, D5),
-                ChessMove::regular(E5, C5),
-                ChessMove::regular(E5, B5),
                 ChessMove::regular(E5, A5),
+                ChessMove::regular(E5, B5),
+                ChessMove::regular(E5, C5),
+                ChessMove::regular(E5, D5),
             ]
         );
 
@@ -1474,14 +2509,14 @@ mod tests {
         // decreasing rank, increasing file
         assert_eq!(
             game.moves_to_opponents_piece(E5, 1, -1, White),
-            vec![ChessMove::regular(E5, F4), ChessMove::regular(E5, G3),]
+            vec![ChessMove::regular(E5, G3), ChessMove::regular(E5, F4),]
         );
 
         // diagonal
         // decreasing rank, decreasing file
         assert_eq!(
             game.moves_to_opponents_piece(E5, -1, -1, White),
-            vec![ChessMove::regular(E5, D4), ChessMove::regular(E5, C3),]
+            vec![ChessMove::regular(E5, C3), ChessMove::regular(E5, D4),]
         );
     }
 
@@ -1617,7 +2652,7 @@ mod tests {
     }
 
     #[test]
-    fn test_undo_last_move() {
+    fn test_unmake_move() {
         let mut game = Game::new();
 
         print_board("initial", &game);
@@ -1630,7 +2665,7 @@ mod tests {
 
         print_board("black pawn to E5", &game);
 
-        game.undo_last_move();
+        game.unmake_move();
 
         print_board("undo last move", &game);
 
@@ -1638,6 +2673,488 @@ mod tests {
         assert!(game.board[E7].piece().is_some());
     }
 
+    #[test]
+    fn test_unmake_move_restores_castling_rights_and_rook() {
+        let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+        let game_before = Game::from_fen(fen).unwrap();
+        let mut game = Game::from_fen(fen).unwrap();
+
+        game.execute_move(ChessMove::castle(E1, G1, H1, F1));
+        assert_eq!(game.board[G1].piece().map(|p| p.piece_type()), Some(PieceType::King));
+        assert_eq!(game.board[F1].piece().map(|p| p.piece_type()), Some(PieceType::Rook));
+
+        game.unmake_move();
+
+        assert_eq!(game.to_fen(), game_before.to_fen());
+    }
+
+    #[test]
+    fn test_unmake_move_restores_pawn_before_promotion() {
+        let fen = "4k3/P7/8/8/8/8/8/4K3 w - - 0 1";
+        let game_before = Game::from_fen(fen).unwrap();
+        let mut game = Game::from_fen(fen).unwrap();
+
+        game.execute_move(ChessMove::promotion(A7, A8, PieceType::Queen));
+        assert_eq!(game.board[A8].piece().map(|p| p.piece_type()), Some(PieceType::Queen));
+
+        game.unmake_move();
+
+        assert_eq!(game.board[A7].piece().map(|p| p.piece_type()), Some(PieceType::Pawn));
+        assert_eq!(game.to_fen(), game_before.to_fen());
+    }
+
+    #[test]
+    fn test_unmake_move_restores_en_passant_victim() {
+        let fen = "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1";
+        let game_before = Game::from_fen(fen).unwrap();
+        let mut game = Game::from_fen(fen).unwrap();
+
+        game.execute_move(ChessMove::en_passant(E5, D6, D5));
+        assert_eq!(game.board[D5].piece(), None);
+        assert_eq!(game.board[D6].piece().map(|p| p.piece_type()), Some(PieceType::Pawn));
+
+        game.unmake_move();
+
+        assert_eq!(game.to_fen(), game_before.to_fen());
+    }
+
+    #[test]
+    fn test_redo_replays_unmade_moves() {
+        let mut game = Game::new();
+
+        game.execute_move(ChessMove::regular(E2, E4));
+        game.execute_move(ChessMove::regular(E7, E5));
+        game.unmake_move();
+        game.unmake_move();
+
+        assert!(game.history().is_empty());
+
+        assert!(game.redo());
+        assert_eq!(game.history(), &[ChessMove::regular(E2, E4)]);
+
+        assert!(game.redo());
+        assert_eq!(
+            game.history(),
+            &[ChessMove::regular(E2, E4), ChessMove::regular(E7, E5)]
+        );
+
+        assert!(!game.redo());
+    }
+
+    #[test]
+    fn test_execute_move_clears_redo_history() {
+        let mut game = Game::new();
+
+        game.execute_move(ChessMove::regular(E2, E4));
+        game.unmake_move();
+
+        game.execute_move(ChessMove::regular(D2, D4));
+
+        assert!(!game.redo());
+        assert_eq!(game.history(), &[ChessMove::regular(D2, D4)]);
+    }
+
+    #[test]
+    fn test_fen_round_trip_starting_position() {
+        let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        let game = Game::from_fen(fen).unwrap();
+        assert_eq!(game.to_fen(), fen);
+    }
+
+    #[test]
+    fn test_perft_starting_position() {
+        // reference counts from https://www.chessprogramming.org/Perft_Results
+        let mut game = Game::new();
+        assert_eq!(game.perft(1), 20);
+        assert_eq!(game.perft(2), 400);
+        assert_eq!(game.perft(3), 8_902);
+        assert_eq!(game.perft(4), 197_281);
+    }
+
+    #[test]
+    fn test_perft_kiwipete_position() {
+        // the "Kiwipete" position, a perft reference position that exercises castling,
+        // en passant, and promotions that the starting position alone doesn't reach.
+        let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+        let mut game = Game::from_fen(fen).unwrap();
+        assert_eq!(game.perft(1), 48);
+        // depth 1 only counts the legal root moves; the deeper counts also cover the replies
+        // after castling, en-passant captures and moves of pinned pieces.
+        assert_eq!(game.perft(2), 2039);
+        assert_eq!(game.perft(3), 97862);
+    }
+
+    #[test]
+    fn test_perft_divide_sums_to_perft() {
+        let mut game = Game::new();
+        let divided = game.perft_divide(3);
+        let total: u64 = divided.iter().map(|(_, nodes)| nodes).sum();
+        assert_eq!(total, game.perft(3));
+    }
+
+    #[test]
+    fn test_fen_round_trip_midgame_position() {
+        // the "Kiwipete" position, commonly used as a perft reference: all four castling
+        // rights, no en-passant target, and advanced pieces on both sides.
+        let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+        let game = Game::from_fen(fen).unwrap();
+        assert_eq!(game.to_fen(), fen);
+    }
+
+    #[test]
+    fn test_fen_round_trip_with_en_passant() {
+        let mut game = Game::new();
+        game.execute_move(ChessMove::regular(E2, E4));
+
+        let fen = game.to_fen();
+        assert_eq!(fen, "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+
+        let reparsed = Game::from_fen(&fen).unwrap();
+        assert_eq!(reparsed.to_fen(), fen);
+    }
+
+    #[test]
+    fn test_from_fen_rejects_wrong_field_count() {
+        assert!(Game::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -").is_err());
+    }
+
+    #[test]
+    fn test_from_fen_rejects_pawn_on_back_rank() {
+        assert_eq!(
+            Game::from_fen("Pnbqkbnr/1ppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap_err(),
+            FenError::InvalidPosition(InvalidError::InvalidPawnPosition(A8))
+        );
+    }
+
+    #[test]
+    fn test_from_fen_rejects_neighbouring_kings() {
+        assert_eq!(
+            Game::from_fen("8/8/8/8/3kK3/8/8/8 w - - 0 1").unwrap_err(),
+            FenError::InvalidPosition(InvalidError::NeighbouringKings)
+        );
+    }
+
+    #[test]
+    fn test_from_fen_rejects_missing_king() {
+        assert_eq!(
+            Game::from_fen("8/8/8/8/4K3/8/8/8 w - - 0 1").unwrap_err(),
+            FenError::InvalidPosition(InvalidError::MissingKing(Black))
+        );
+    }
+
+    #[test]
+    fn test_from_fen_rejects_castling_rights_without_rook() {
+        assert_eq!(
+            Game::from_fen("4k3/8/8/8/8/8/8/4K3 w KQkq - 0 1").unwrap_err(),
+            FenError::InvalidPosition(InvalidError::InvalidCastlingRights)
+        );
+    }
+
+    #[test]
+    fn test_from_fen_rejects_bogus_en_passant_target() {
+        assert_eq!(
+            Game::from_fen("4k3/8/8/8/8/8/8/4K3 w - e3 0 1").unwrap_err(),
+            FenError::InvalidPosition(InvalidError::InvalidEnPassant(E3))
+        );
+    }
+
+    #[test]
+    fn test_is_draw_by_repetition() {
+        let mut game = Game::new();
+        assert!(!game.is_draw_by_repetition());
+
+        // shuffle knights back and forth to repeat the starting position three times in total.
+        for _ in 0..2 {
+            game.execute_move(ChessMove::regular(G1, F3));
+            game.execute_move(ChessMove::regular(G8, F6));
+            game.execute_move(ChessMove::regular(F3, G1));
+            game.execute_move(ChessMove::regular(F6, G8));
+        }
+
+        assert!(game.is_draw_by_repetition());
+    }
+
+    #[test]
+    fn test_is_draw_by_fifty_moves() {
+        let mut game = Game::new();
+        assert!(!game.is_draw_by_fifty_moves());
+
+        for _ in 0..25 {
+            game.execute_move(ChessMove::regular(G1, F3));
+            game.execute_move(ChessMove::regular(G8, F6));
+            game.execute_move(ChessMove::regular(F3, G1));
+            game.execute_move(ChessMove::regular(F6, G8));
+        }
+
+        assert!(game.is_draw_by_fifty_moves());
+    }
+
+    #[test]
+    fn test_is_draw_by_insufficient_material() {
+        // king vs. king
+        assert!(Game::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1")
+            .unwrap()
+            .is_draw_by_insufficient_material());
+
+        // king and bishop vs. king
+        assert!(Game::from_fen("4k3/8/8/8/8/8/8/4KB2 w - - 0 1")
+            .unwrap()
+            .is_draw_by_insufficient_material());
+
+        // king and bishop vs. king and bishop, same-colored bishops
+        assert!(Game::from_fen("2b1k3/8/8/8/8/8/8/4KB2 w - - 0 1")
+            .unwrap()
+            .is_draw_by_insufficient_material());
+
+        // king and bishop vs. king and bishop, opposite-colored bishops
+        assert!(!Game::from_fen("3bk3/8/8/8/8/8/8/4KB2 w - - 0 1")
+            .unwrap()
+            .is_draw_by_insufficient_material());
+
+        // king and rook vs. king is enough material to mate with
+        assert!(!Game::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1")
+            .unwrap()
+            .is_draw_by_insufficient_material());
+    }
+
+    #[test]
+    fn test_outcome_checkmate() {
+        let mut game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R6K w - - 0 1").unwrap();
+        assert_eq!(game.outcome(), None);
+
+        game.make_move(A1, A8, None).unwrap();
+        assert_eq!(
+            game.outcome(),
+            Some(Outcome::Decisive {
+                winner: Color::White
+            })
+        );
+    }
+
+    #[test]
+    fn test_outcome_stalemate() {
+        let game = Game::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+        assert_eq!(game.outcome(), Some(Outcome::Draw));
+    }
+
+    #[test]
+    fn test_outcome_insufficient_material() {
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(game.outcome(), Some(Outcome::Draw));
+    }
+
+    #[test]
+    fn test_incremental_zobrist_hash_matches_from_scratch() {
+        fn assert_hash_matches(game: &Game) {
+            assert_eq!(
+                game.position_hash(),
+                compute_zobrist_hash(
+                    &game.board,
+                    game.side_to_move,
+                    game.castling_rights,
+                    game.en_passant_target,
+                )
+            );
+        }
+
+        fn assert_incremental_hash_survives_make_unmake(fen: &str, chess_move: ChessMove) {
+            let mut game = Game::from_fen(fen).unwrap();
+            assert_hash_matches(&game);
+
+            game.execute_move(chess_move);
+            assert_hash_matches(&game);
+
+            game.unmake_move();
+            assert_hash_matches(&game);
+        }
+
+        // a plain capture
+        assert_incremental_hash_survives_make_unmake(
+            "4k3/8/8/4p3/4P3/8/8/4K3 w - - 0 1",
+            ChessMove::regular(E4, E5),
+        );
+
+        // castling kingside
+        assert_incremental_hash_survives_make_unmake(
+            "4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1",
+            ChessMove::castle(E1, G1, H1, F1),
+        );
+
+        // an en passant capture
+        assert_incremental_hash_survives_make_unmake(
+            "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1",
+            ChessMove::en_passant(E5, D6, D5),
+        );
+
+        // a promotion
+        assert_incremental_hash_survives_make_unmake(
+            "4k3/P7/8/8/8/8/8/4K3 w - - 0 1",
+            ChessMove::promotion(A7, A8, PieceType::Queen),
+        );
+    }
+
+    #[test]
+    fn test_best_move_finds_mate_in_one() {
+        let mut game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R6K w - - 0 1").unwrap();
+
+        let best = game
+            .best_move(1)
+            .expect("white has a back-rank mate in one here");
+
+        game.execute_move(best);
+
+        assert!(game.is_king_checked(Black));
+        assert!(game.legal_moves().is_empty());
+    }
+
+    #[test]
+    fn test_best_move_prefers_a_free_capture() {
+        let game = Game::from_fen("7k/8/8/b7/8/8/8/R6K w - - 0 1").unwrap();
+
+        let best = game.best_move(1).unwrap();
+
+        assert_eq!(best, ChessMove::regular(A1, A5));
+    }
+
+    #[test]
+    fn test_best_move_with_custom_evaluator() {
+        // an evaluator that only cares about material balance, with no centre bonus, to check
+        // that `best_move_with` actually drives the search with the evaluator it's given.
+        struct PieceCountEvaluator;
+        impl Evaluator for PieceCountEvaluator {
+            fn evaluate(&self, game: &Game, side_to_move: Color) -> i32 {
+                let mut white_relative_count = 0;
+                for rank in RankIter::start_at(Rank::First) {
+                    for file in FileIter::start_at(File::A) {
+                        if let Some(piece) = game.board[ChessIndex::new(file, rank)].piece() {
+                            white_relative_count += match piece.color() {
+                                Color::White => 1,
+                                Color::Black => -1,
+                            };
+                        }
+                    }
+                }
+
+                match side_to_move {
+                    Color::White => white_relative_count,
+                    Color::Black => -white_relative_count,
+                }
+            }
+        }
+
+        let game = Game::from_fen("7k/8/8/b7/8/8/8/R6K w - - 0 1").unwrap();
+
+        let best = game.best_move_with(&PieceCountEvaluator, 1).unwrap();
+
+        assert_eq!(best, ChessMove::regular(A1, A5));
+    }
+
+    #[test]
+    fn test_parse_uci_round_trips_with_to_uci() {
+        let game = Game::new();
+
+        let chess_move = game.parse_uci("e2e4").unwrap();
+
+        assert_eq!(chess_move, ChessMove::regular(E2, E4));
+        assert_eq!(chess_move.to_uci(), "e2e4");
+    }
+
+    #[test]
+    fn test_parse_uci_promotion() {
+        let game = Game::from_fen("8/P6k/8/8/8/8/8/7K w - - 0 1").unwrap();
+
+        let chess_move = game.parse_uci("a7a8q").unwrap();
+
+        assert_eq!(chess_move, ChessMove::promotion(A7, A8, PieceType::Queen));
+    }
+
+    #[test]
+    fn test_parse_uci_rejects_illegal_move() {
+        let game = Game::new();
+
+        assert_eq!(game.parse_uci("e2e5"), Err(ParseUciError::NoSuchMove));
+    }
+
+    #[test]
+    fn test_move_to_san_knight_and_pawn_capture() {
+        let mut game = Game::new();
+
+        assert_eq!(
+            game.move_to_san(ChessMove::regular(G1, F3)),
+            "Nf3".to_string()
+        );
+        game.execute_move(ChessMove::regular(G1, F3));
+        game.execute_move(ChessMove::regular(D7, D5));
+
+        assert_eq!(
+            game.move_to_san(ChessMove::regular(F3, E5)),
+            "Ne5".to_string()
+        );
+        game.execute_move(ChessMove::regular(E2, E4));
+
+        assert_eq!(
+            game.move_to_san(ChessMove::regular(D5, E4)),
+            "dxe4".to_string()
+        );
+    }
+
+    #[test]
+    fn test_move_to_san_castle_and_checkmate() {
+        let game = Game::from_fen("6k1/5ppp/8/8/8/8/8/R6K w - - 0 1").unwrap();
+
+        assert_eq!(
+            game.move_to_san(ChessMove::regular(A1, A8)),
+            "Ra8#".to_string()
+        );
+
+        let mut game = Game::new();
+        game.board[F1].clear();
+        game.board[G1].clear();
+
+        assert_eq!(
+            game.move_to_san(ChessMove::castle(E1, G1, H1, F1)),
+            "O-O".to_string()
+        );
+    }
+
+    #[test]
+    fn test_move_to_san_disambiguates_by_file() {
+        let game = Game::from_fen("4k3/8/8/8/4K3/8/8/R6R w - - 0 1").unwrap();
+
+        assert_eq!(
+            game.move_to_san(ChessMove::regular(A1, D1)),
+            "Rad1".to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_san_round_trips_with_move_to_san() {
+        let mut game = Game::new();
+
+        let chess_move = game.parse_san("Nf3").unwrap();
+        assert_eq!(chess_move, ChessMove::regular(G1, F3));
+        assert_eq!(game.move_to_san(chess_move), "Nf3".to_string());
+
+        game.execute_move(chess_move);
+        game.execute_move(ChessMove::regular(D7, D5));
+
+        let chess_move = game.parse_san("d4").unwrap();
+        assert_eq!(chess_move, ChessMove::regular(D2, D4));
+        assert_eq!(game.move_to_san(chess_move), "d4".to_string());
+    }
+
+    #[test]
+    fn test_parse_san_castle() {
+        let mut game = Game::new();
+        game.board[F1].clear();
+        game.board[G1].clear();
+
+        assert_eq!(
+            game.parse_san("O-O").unwrap(),
+            ChessMove::castle(E1, G1, H1, F1)
+        );
+    }
+
     fn print_board(title: &str, game: &Game) {
         println!("{}:", title);
         println!("{}", game.board);
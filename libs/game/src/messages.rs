@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{ChessIndex, PieceType};
+
+/// The JSON wire message spoken between a `clichess-server` room and anything connected to it —
+/// `clichess-server`'s `Lobby`/`WsConn` on one end, `PlayOnline` on the other — shared here so
+/// both sides agree on the same type instead of keeping parallel, driftable copies. A move a
+/// seated player is attempting, and the board the room settles into after a move is accepted
+/// (or right after a seat is filled). This is the only structured part of the protocol;
+/// join/error/status notices stay the plain text they always were.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ChessMessage {
+    /// A move from `from` to `to`. `promotion` is required exactly when `to` lands on a pawn's
+    /// promotion rank, same as `ChessMove::Promotion`; a missing or superfluous `promotion` just
+    /// fails to match anything when the receiver resolves it against its own legal moves.
+    /// Sent by a seated player attempting a move, and broadcast back by the room once that move
+    /// is accepted, so everyone connected learns which move was actually played.
+    Move {
+        from: ChessIndex,
+        to: ChessIndex,
+        promotion: Option<PieceType>,
+    },
+    /// The room's board as the piece-placement and side-to-move fields of FEN (`ChessBoard`
+    /// doesn't track castling rights, en-passant target, or the move clocks itself, so this is
+    /// shorter than `Game::to_fen`'s full six fields).
+    BoardState { fen: String },
+}
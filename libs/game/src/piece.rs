@@ -26,6 +26,40 @@ impl Piece {
         self.color
     }
 
+    /// The squares this piece has occupied, in order, including its current square.
+    pub fn history(&self) -> &Vec<ChessIndex> {
+        &self.history
+    }
+
+    pub(crate) fn add_index_to_history(&mut self, index: ChessIndex) {
+        self.history.push(index);
+    }
+
+    /// Reverses the last `add_index_to_history` push. Used by `Game::unmake_move` to restore a
+    /// piece's move history when putting it back on its origin square in place.
+    pub(crate) fn pop_index_from_history(&mut self) {
+        self.history.pop();
+    }
+
+    /// Used when reconstructing a position (e.g. from FEN) to record the square a piece is
+    /// known to have come from, without having actually played out the move.
+    pub(crate) fn set_previous_index(&mut self, origin: ChessIndex) {
+        self.history.insert(0, origin);
+    }
+
+    /// The square this piece occupied before its current one, if it has moved at least once.
+    pub fn previous_index(&self) -> Option<ChessIndex> {
+        if self.history.len() >= 2 {
+            Some(self.history[self.history.len() - 2])
+        } else {
+            None
+        }
+    }
+
+    pub fn has_made_move(&self) -> bool {
+        self.history.len() > 1
+    }
+
     pub fn is_pawn(&self) -> bool {
         match self.piece_type() {
             PieceType::Pawn => true,
@@ -113,7 +147,7 @@ impl Display for Piece {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Debug, Eq)]
+#[derive(Clone, Copy, PartialEq, Debug, Eq, serde::Serialize, serde::Deserialize)]
 pub enum PieceType {
     Pawn,
     Knight,
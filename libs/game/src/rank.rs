@@ -7,7 +7,7 @@ use std::{
 use crate::Color;
 
 /// A chess rank (horizontal line)
-#[derive(Debug, Copy, PartialEq, Clone, Eq)]
+#[derive(Debug, Copy, PartialEq, Clone, Eq, serde::Serialize, serde::Deserialize)]
 pub enum Rank {
     First,
     Second,
@@ -35,6 +35,14 @@ impl Rank {
             _ => false,
         }
     }
+
+    /// This rank's 8 squares as a 64-bit mask, one bit per square in the same `a1` = bit 0,
+    /// `h8` = bit 63 ordering `Bitboard` and `ChessIndex`'s `u64` conversion use — e.g.
+    /// `Rank::First` sets bits 0 through 7. Delegates to `bitboard::rank_mask` so there's a
+    /// single source of truth for the bit layout.
+    pub fn mask(&self) -> u64 {
+        crate::rank_mask(*self).bits()
+    }
 }
 
 impl Add<u8> for Rank {
@@ -0,0 +1,279 @@
+use crate::{ai::Strategy, ChessMove, Game};
+use std::{
+    cell::RefCell,
+    error::Error,
+    fmt::Display,
+    io::{BufRead, BufReader, Write},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+};
+
+/// Per-side time-control tokens for UCI's `go` command. Leave a field `None` to omit its token;
+/// setting `movetime` is the simplest way to cap a single search, while `wtime`/`btime` (plus
+/// the optional increments) let the engine manage its own clock the way a real game would.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeControl {
+    pub wtime: Option<u32>,
+    pub btime: Option<u32>,
+    pub winc: Option<u32>,
+    pub binc: Option<u32>,
+    pub movetime: Option<u32>,
+}
+
+impl TimeControl {
+    /// A single search capped at `millis` regardless of side to move, the common case for an
+    /// engine that's just handing back one move at a time.
+    pub fn move_time(millis: u32) -> Self {
+        Self {
+            movetime: Some(millis),
+            ..Self::default()
+        }
+    }
+
+    fn go_command(&self) -> String {
+        let mut command = "go".to_string();
+        if let Some(movetime) = self.movetime {
+            command.push_str(&format!(" movetime {}", movetime));
+        }
+        if let Some(wtime) = self.wtime {
+            command.push_str(&format!(" wtime {}", wtime));
+        }
+        if let Some(btime) = self.btime {
+            command.push_str(&format!(" btime {}", btime));
+        }
+        if let Some(winc) = self.winc {
+            command.push_str(&format!(" winc {}", winc));
+        }
+        if let Some(binc) = self.binc {
+            command.push_str(&format!(" binc {}", binc));
+        }
+        command
+    }
+}
+
+/// Builder for the handful of `setoption` commands `UciEngine::spawn` sends before the engine is
+/// put into the game loop: limiting playing strength to an approximate Elo, and whether the
+/// engine is allowed to ponder on the opponent's time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UciEngineOptions {
+    limit_elo: Option<u32>,
+    ponder: bool,
+}
+
+impl UciEngineOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends `setoption name UCI_LimitStrength value true` followed by
+    /// `setoption name UCI_Elo value <elo>`, so the engine plays down to roughly `elo`.
+    pub fn limit_elo(mut self, elo: u32) -> Self {
+        self.limit_elo = Some(elo);
+        self
+    }
+
+    pub fn ponder(mut self, ponder: bool) -> Self {
+        self.ponder = ponder;
+        self
+    }
+}
+
+#[derive(Debug)]
+pub enum UciError {
+    /// The engine's executable couldn't be spawned as a child process.
+    Spawn(std::io::Error),
+    /// Writing to or reading from the engine's stdin/stdout failed.
+    Io(std::io::Error),
+    /// The engine's stdout closed before the expected response line arrived.
+    UnexpectedEof,
+    /// The engine's `bestmove` line didn't parse into a legal move in the position it was asked
+    /// to search.
+    InvalidMove(String),
+}
+
+impl Display for UciError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let output = match self {
+            UciError::Spawn(err) => format!("failed to spawn UCI engine: {}", err),
+            UciError::Io(err) => format!("I/O error talking to UCI engine: {}", err),
+            UciError::UnexpectedEof => {
+                "UCI engine closed its output before responding".to_string()
+            }
+            UciError::InvalidMove(uci) => format!("UCI engine proposed an illegal move: '{}'", uci),
+        };
+
+        write!(f, "{}", output)
+    }
+}
+
+impl Error for UciError {}
+
+/// A UCI-speaking chess engine (e.g. Stockfish) driven as a child process. `spawn` performs the
+/// `uci`/`isready`/`ucinewgame` handshake up front; `best_move` then drives one search per call,
+/// re-sending the whole position as FEN since `Game` doesn't track which moves an external
+/// engine has already been told about.
+pub struct UciEngine {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl UciEngine {
+    /// Spawns `command` with no arguments and performs the UCI handshake, applying `options` via
+    /// `setoption` before the final `isready`/`readyok` round-trip.
+    pub fn spawn(command: &str, options: UciEngineOptions) -> Result<Self, UciError> {
+        let mut child = Command::new(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(UciError::Spawn)?;
+
+        let stdin = child.stdin.take().expect("child spawned with piped stdin");
+        let stdout = BufReader::new(
+            child
+                .stdout
+                .take()
+                .expect("child spawned with piped stdout"),
+        );
+
+        let mut engine = Self {
+            child,
+            stdin,
+            stdout,
+        };
+
+        engine.write_line("uci")?;
+        engine.read_until(|line| line == "uciok")?;
+
+        if let Some(elo) = options.limit_elo {
+            engine.write_line("setoption name UCI_LimitStrength value true")?;
+            engine.write_line(&format!("setoption name UCI_Elo value {}", elo))?;
+        }
+        engine.write_line(&format!(
+            "setoption name Ponder value {}",
+            options.ponder
+        ))?;
+
+        engine.write_line("isready")?;
+        engine.read_until(|line| line == "readyok")?;
+
+        engine.write_line("ucinewgame")?;
+
+        Ok(engine)
+    }
+
+    /// Asks the engine for its best move in `game`'s current position under `time_control`.
+    /// Drains a fresh `isready`/`readyok` round-trip first, so a search left running by a
+    /// previous call can't bleed its output into this one's `bestmove` line. Returns `Ok(None)`
+    /// for `bestmove (none)`, which engines send when asked to search a position with no legal
+    /// moves.
+    pub fn best_move(
+        &mut self,
+        game: &Game,
+        time_control: TimeControl,
+    ) -> Result<Option<ChessMove>, UciError> {
+        self.write_line("isready")?;
+        self.read_until(|line| line == "readyok")?;
+
+        self.write_line(&format!("position fen {}", game.to_fen()))?;
+        self.write_line(&time_control.go_command())?;
+
+        let bestmove_line = self.read_until(|line| line.starts_with("bestmove"))?;
+        let uci_move = bestmove_line
+            .split_whitespace()
+            .nth(1)
+            .ok_or_else(|| UciError::InvalidMove(bestmove_line.clone()))?;
+
+        if uci_move == "(none)" {
+            return Ok(None);
+        }
+
+        // `parse_uci` resolves the move against `valid_moves_from`, which is how the engine's
+        // castling king-slide notation (e.g. `e1g1`) turns back into a `ChessMove::Castle`.
+        game.parse_uci(uci_move)
+            .map(Some)
+            .map_err(|_| UciError::InvalidMove(uci_move.to_string()))
+    }
+
+    fn write_line(&mut self, line: &str) -> Result<(), UciError> {
+        writeln!(self.stdin, "{}", line).map_err(UciError::Io)
+    }
+
+    fn read_until(&mut self, matches: impl Fn(&str) -> bool) -> Result<String, UciError> {
+        loop {
+            let mut line = String::new();
+            let bytes_read = self.stdout.read_line(&mut line).map_err(UciError::Io)?;
+            if bytes_read == 0 {
+                return Err(UciError::UnexpectedEof);
+            }
+
+            let line = line.trim_end().to_string();
+            if matches(&line) {
+                return Ok(line);
+            }
+        }
+    }
+}
+
+impl Drop for UciEngine {
+    fn drop(&mut self) {
+        let _ = self.write_line("quit");
+        let _ = self.child.wait();
+    }
+}
+
+/// Adapts a `UciEngine` into a `Strategy`, so `ComputerPlayer<UciStrategy>` can hand
+/// `PlayLocal`'s `VsComputerAsBlack`/`VsComputerAsWhite` modes off to an external engine.
+/// `Strategy::get_move` takes `&self`, but driving the engine needs `&mut self` (it's writing to
+/// and reading from a child process), hence the `RefCell`.
+pub struct UciStrategy {
+    engine: RefCell<UciEngine>,
+    time_control: TimeControl,
+}
+
+impl UciStrategy {
+    pub fn new(engine: UciEngine, time_control: TimeControl) -> Self {
+        Self {
+            engine: RefCell::new(engine),
+            time_control,
+        }
+    }
+}
+
+impl Strategy for UciStrategy {
+    fn get_move(&self, game: &Game) -> ChessMove {
+        self.engine
+            .borrow_mut()
+            .best_move(game, self.time_control)
+            .expect("UCI engine I/O failed")
+            .expect("UCI engine reported no legal move in a position that has one")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_control_go_command() {
+        assert_eq!(TimeControl::default().go_command(), "go");
+        assert_eq!(TimeControl::move_time(500).go_command(), "go movetime 500");
+        assert_eq!(
+            TimeControl {
+                wtime: Some(60_000),
+                btime: Some(60_000),
+                winc: Some(1_000),
+                binc: Some(1_000),
+                movetime: None,
+            }
+            .go_command(),
+            "go wtime 60000 btime 60000 winc 1000 binc 1000"
+        );
+    }
+
+    #[test]
+    fn test_uci_engine_options_builder() {
+        let options = UciEngineOptions::new().limit_elo(1500).ponder(true);
+        assert_eq!(options.limit_elo, Some(1500));
+        assert!(options.ponder);
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{ChessIndex, Color, File, PieceType};
+
+/// Deterministic pseudo-random `u64` keys for Zobrist hashing.
+///
+/// Rather than precomputing and storing a 12x64 (+ state) table, each key is derived on demand
+/// from its (piece type, color, square) via `splitmix64` seeded on those components. This gives
+/// the same "independent-looking, stable across runs" property a precomputed table would, with
+/// no startup table-build step.
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+pub fn piece_key(piece_type: PieceType, color: Color, index: ChessIndex) -> u64 {
+    let piece_index = piece_type as u64;
+    let color_index = match color {
+        Color::White => 0u64,
+        Color::Black => 1u64,
+    };
+    let square = index.linear_value() as u64;
+    // one distinct seed per (square, piece type, color), all below the state keys' seeds; since
+    // `splitmix64` is a bijection, no two keys coincide.
+    let seed = square * 12 + piece_index * 2 + color_index;
+    splitmix64(seed)
+}
+
+pub fn side_to_move_key() -> u64 {
+    splitmix64(0xFEED_u64)
+}
+
+/// `which` indexes the four castling rights: 0 = white kingside, 1 = white queenside,
+/// 2 = black kingside, 3 = black queenside.
+pub fn castling_key(which: u8) -> u64 {
+    splitmix64(0xC0DE_u64.wrapping_add(which as u64))
+}
+
+pub fn en_passant_file_key(file: File) -> u64 {
+    splitmix64(0xE9A5_u64.wrapping_add(u8::from(&file) as u64))
+}
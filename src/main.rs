@@ -2,8 +2,11 @@ mod modes;
 
 use std::collections::HashSet;
 
-use chess::ChessBoard;
-use modes::{ComputerPlayer, LocalPlayer, PlayLocalMode, PlayLocalOpts};
+use chess::{ChessBoard, TimeControl, UciEngine, UciEngineOptions, UciStrategy};
+use modes::{
+    ComputerPlayer, LocalPlayer, PlayLocalMode, PlayLocalOpts, PlayOnline, PlayOnlineOpts,
+    TuiPlayer,
+};
 use structopt::StructOpt;
 
 #[derive(StructOpt, Debug)]
@@ -15,37 +18,77 @@ struct Opts {
 #[derive(Debug, StructOpt)]
 enum Command {
     PlayLocal(PlayLocalOpts),
+    PlayOnline(PlayOnlineOpts),
 }
 
 fn main() {
     let opts: Opts = Opts::from_args();
 
     match opts.command {
-        Command::PlayLocal(opts) => match opts.mode() {
-            PlayLocalMode::VsHuman => {
+        Command::PlayLocal(opts) => match (opts.mode(), opts.tui()) {
+            (PlayLocalMode::VsHuman, true) => {
+                let mut game = modes::PlayLocal::new(opts, TuiPlayer::new(), TuiPlayer::new());
+                game.play().unwrap();
+            }
+            (PlayLocalMode::VsHuman, false) => {
                 let mut game = modes::PlayLocal::new(opts, LocalPlayer::new(), LocalPlayer::new());
                 game.play().unwrap();
             }
-            PlayLocalMode::VsComputerAsBlack => {
+            (PlayLocalMode::VsComputerAsBlack, true) => {
+                let strategy = spawn_uci_strategy(&opts);
+                let mut game = modes::PlayLocal::new(
+                    opts,
+                    ComputerPlayer::new(strategy),
+                    TuiPlayer::new(),
+                );
+                game.play().unwrap();
+            }
+            (PlayLocalMode::VsComputerAsBlack, false) => {
+                let strategy = spawn_uci_strategy(&opts);
                 let mut game = modes::PlayLocal::new(
                     opts,
-                    ComputerPlayer::new(chess::ai::Material {}),
+                    ComputerPlayer::new(strategy),
                     LocalPlayer::new(),
                 );
                 game.play().unwrap();
             }
-            PlayLocalMode::VsComputerAsWhite => {
+            (PlayLocalMode::VsComputerAsWhite, true) => {
+                let strategy = spawn_uci_strategy(&opts);
+                let mut game = modes::PlayLocal::new(
+                    opts,
+                    TuiPlayer::new(),
+                    ComputerPlayer::new(strategy),
+                );
+                game.play().unwrap();
+            }
+            (PlayLocalMode::VsComputerAsWhite, false) => {
+                let strategy = spawn_uci_strategy(&opts);
                 let mut game = modes::PlayLocal::new(
                     opts,
                     LocalPlayer::new(),
-                    ComputerPlayer::new(chess::ai::Material {}),
+                    ComputerPlayer::new(strategy),
                 );
                 game.play().unwrap();
             }
         },
+        Command::PlayOnline(opts) => {
+            let mut game = PlayOnline::join(opts, LocalPlayer::new()).unwrap();
+            game.play().unwrap();
+        }
     }
 }
 
+/// Spawns the `--engine` command as a `UciStrategy` for `ComputerPlayer`; vs-computer modes
+/// have nothing else to fall back to, so a missing `--engine` or a failed spawn is fatal.
+fn spawn_uci_strategy(opts: &PlayLocalOpts) -> UciStrategy {
+    let command = opts
+        .engine()
+        .expect("--engine <command> is required for vs-computer modes");
+    let engine =
+        UciEngine::spawn(command, UciEngineOptions::new()).expect("failed to spawn UCI engine");
+    UciStrategy::new(engine, TimeControl::move_time(opts.movetime()))
+}
+
 fn print_whites_perspective(board: &ChessBoard) {
     println!("---");
     println!("{}", chess::fmt::whites_perspective(board, &HashSet::new()));
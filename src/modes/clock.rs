@@ -0,0 +1,151 @@
+use std::{
+    fmt::Display,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use chess::{Color, TimeControl};
+
+/// Initial time and increment for one side of a `Clock`, parsed from the CLI as
+/// `<minutes>+<increment-seconds>` (e.g. `5+3` for five minutes with a three-second increment
+/// per move).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockConfig {
+    initial: Duration,
+    increment: Duration,
+}
+
+impl FromStr for ClockConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (minutes, increment_secs) = s
+            .split_once('+')
+            .ok_or_else(|| format!("expected '<minutes>+<increment-seconds>', got '{}'", s))?;
+        let minutes: u64 = minutes
+            .parse()
+            .map_err(|_| format!("invalid minutes: '{}'", minutes))?;
+        let increment_secs: u64 = increment_secs
+            .parse()
+            .map_err(|_| format!("invalid increment: '{}'", increment_secs))?;
+
+        Ok(Self {
+            initial: Duration::from_secs(minutes * 60),
+            increment: Duration::from_secs(increment_secs),
+        })
+    }
+}
+
+/// How a `PlayLocal` game ended on the clock rather than on the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockResult {
+    /// The held side wins: its opponent flagged (ran its clock to zero) while it still had
+    /// mating material.
+    WinOnTime(Color),
+    /// A side flagged, but the opponent had insufficient material to ever deliver mate.
+    DrawInsufficientMaterial,
+}
+
+impl Display for ClockResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClockResult::WinOnTime(winner) => write!(f, "{} wins on time", winner),
+            ClockResult::DrawInsufficientMaterial => {
+                write!(f, "draw, flag fell but mate was impossible")
+            }
+        }
+    }
+}
+
+/// Two independent countdown clocks, one per side. `PlayLocal::single_turn` starts the side to
+/// move's clock at the top of their turn and stops it (crediting their increment) once they've
+/// committed a move.
+pub struct Clock {
+    white_remaining: Duration,
+    black_remaining: Duration,
+    white_increment: Duration,
+    black_increment: Duration,
+    running: Option<(Color, Instant)>,
+}
+
+impl Clock {
+    pub fn new(white: ClockConfig, black: ClockConfig) -> Self {
+        Self {
+            white_remaining: white.initial,
+            black_remaining: black.initial,
+            white_increment: white.increment,
+            black_increment: black.increment,
+            running: None,
+        }
+    }
+
+    /// Starts `player`'s countdown. Idempotent if `player`'s clock is already running.
+    pub fn start(&mut self, player: Color) {
+        if !matches!(self.running, Some((running, _)) if running == player) {
+            self.running = Some((player, Instant::now()));
+        }
+    }
+
+    /// Stops whichever clock is running, deducting the elapsed time and crediting that side's
+    /// increment. A clock the elapsed time ran out stays at zero, with no increment. A no-op if
+    /// no clock is currently running.
+    pub fn stop(&mut self) {
+        if let Some((player, since)) = self.running.take() {
+            let elapsed = Instant::now().duration_since(since);
+            let increment = match player {
+                Color::White => self.white_increment,
+                Color::Black => self.black_increment,
+            };
+            let remaining = self.remaining_mut(player);
+            *remaining = if elapsed >= *remaining {
+                Duration::default()
+            } else {
+                *remaining - elapsed + increment
+            };
+        }
+    }
+
+    /// `player`'s remaining time, accounting for a clock currently running.
+    pub fn remaining(&self, player: Color) -> Duration {
+        let remaining = match player {
+            Color::White => self.white_remaining,
+            Color::Black => self.black_remaining,
+        };
+        match self.running {
+            Some((running, since)) if running == player => {
+                remaining.saturating_sub(Instant::now().duration_since(since))
+            }
+            _ => remaining,
+        }
+    }
+
+    /// The side whose clock has hit zero, if any.
+    pub fn flagged(&self) -> Option<Color> {
+        if self.remaining(Color::White) == Duration::default() {
+            Some(Color::White)
+        } else if self.remaining(Color::Black) == Duration::default() {
+            Some(Color::Black)
+        } else {
+            None
+        }
+    }
+
+    /// Both sides' remaining time and increments, in the shape `UciEngine::best_move` expects
+    /// for its `wtime`/`btime`/`winc`/`binc` tokens.
+    pub fn as_time_control(&self) -> TimeControl {
+        TimeControl {
+            wtime: Some(self.remaining(Color::White).as_millis() as u32),
+            btime: Some(self.remaining(Color::Black).as_millis() as u32),
+            winc: Some(self.white_increment.as_millis() as u32),
+            binc: Some(self.black_increment.as_millis() as u32),
+            movetime: None,
+        }
+    }
+
+    fn remaining_mut(&mut self, player: Color) -> &mut Duration {
+        match player {
+            Color::White => &mut self.white_remaining,
+            Color::Black => &mut self.black_remaining,
+        }
+    }
+}
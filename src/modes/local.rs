@@ -1,18 +1,89 @@
 use chess::{Color, Game};
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
 use structopt::StructOpt;
 
-use super::Player;
+use super::{Clock, ClockConfig, ClockResult, Player};
+
+/// How a `PlayLocal` game ended, returned by `PlayLocal::game_over` so callers can print the
+/// actual outcome instead of just naming a winner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameResult {
+    WhiteWins,
+    BlackWins,
+    Draw(DrawReason),
+}
+
+impl Display for GameResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameResult::WhiteWins => write!(f, "checkmate, White wins"),
+            GameResult::BlackWins => write!(f, "checkmate, Black wins"),
+            GameResult::Draw(reason) => write!(f, "draw by {}", reason),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawReason {
+    Stalemate,
+    FiftyMoveRule,
+    ThreefoldRepetition,
+    InsufficientMaterial,
+}
+
+impl Display for DrawReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let output = match self {
+            DrawReason::Stalemate => "stalemate",
+            DrawReason::FiftyMoveRule => "the fifty-move rule",
+            DrawReason::ThreefoldRepetition => "threefold repetition",
+            DrawReason::InsufficientMaterial => "insufficient material",
+        };
+        write!(f, "{}", output)
+    }
+}
 
 #[derive(Debug, StructOpt)]
 pub struct PlayLocalOpts {
     mode: PlayLocalMode,
+    /// Drive human turns with the raw-terminal `TuiPlayer` (arrow keys + Enter) instead of
+    /// `LocalPlayer`'s line-by-line square prompts.
+    #[structopt(long)]
+    tui: bool,
+    /// Time control shared by both sides, as `<minutes>+<increment-seconds>` (e.g. `5+3`).
+    /// Omitted, the game is untimed, as before.
+    #[structopt(long)]
+    clock: Option<ClockConfig>,
+    /// Command of a UCI-speaking engine (e.g. `stockfish`) to drive the computer side of
+    /// `vs-computer-as-black`/`vs-computer-as-white`; required for those modes, ignored by
+    /// `vs-human`.
+    #[structopt(long)]
+    engine: Option<String>,
+    /// Per-move search budget in milliseconds handed to `--engine`'s `go movetime`.
+    #[structopt(long, default_value = "500")]
+    movetime: u32,
 }
 
 impl PlayLocalOpts {
     pub fn mode(&self) -> PlayLocalMode {
         self.mode
     }
+
+    pub fn tui(&self) -> bool {
+        self.tui
+    }
+
+    pub fn clock(&self) -> Option<ClockConfig> {
+        self.clock
+    }
+
+    pub fn engine(&self) -> Option<&str> {
+        self.engine.as_deref()
+    }
+
+    pub fn movetime(&self) -> u32 {
+        self.movetime
+    }
 }
 
 #[derive(Debug, StructOpt, Clone, Copy)]
@@ -41,6 +112,7 @@ pub struct PlayLocal<A, B> {
     black_player: B,
 
     game: Game,
+    clock: Option<Clock>,
 }
 
 impl<A, B> PlayLocal<A, B>
@@ -49,11 +121,13 @@ where
     B: Player,
 {
     pub fn new(opts: PlayLocalOpts, white_player: A, black_player: B) -> Self {
+        let clock = opts.clock().map(|config| Clock::new(config, config));
         Self {
             opts,
             white_player,
             black_player,
             game: Game::new(),
+            clock,
         }
     }
 
@@ -64,31 +138,113 @@ where
     pub fn play(&mut self) -> Result<(), ()> {
         loop {
             crate::print_whites_perspective(self.game().board());
+            self.print_clock();
             self.single_turn(Color::White);
-            if self.game_over() {
-                println!("White wins");
+            if let Some(result) = self.clock_result() {
+                println!("{}", result);
+                return Ok(());
+            }
+            if let Some(result) = self.game_over() {
+                println!("{}", result);
                 return Ok(());
             }
 
             crate::print_blacks_perspective(&self.game().board());
+            self.print_clock();
             self.single_turn(Color::Black);
-            if self.game_over() {
-                println!("Black wins");
+            if let Some(result) = self.clock_result() {
+                println!("{}", result);
+                return Ok(());
+            }
+            if let Some(result) = self.game_over() {
+                println!("{}", result);
                 return Ok(());
             }
         }
     }
 
-    fn game_over(&self) -> bool {
-        false
+    fn game_over(&self) -> Option<GameResult> {
+        game_over(&self.game)
+    }
+
+    /// `Some` once a clock has hit zero, distinguishing a loss on time from a draw if the
+    /// opponent couldn't have mated regardless.
+    fn clock_result(&self) -> Option<ClockResult> {
+        let clock = self.clock.as_ref()?;
+        let flagged = clock.flagged()?;
+        let winner = flagged.opponent();
+        let board = self.game.board();
+        let winner_pieces: Vec<chess::PieceType> = board
+            .occupancy_for(winner)
+            .squares()
+            .filter_map(|idx| board.piece_at(idx))
+            .map(|piece| piece.piece_type())
+            .filter(|piece_type| !matches!(piece_type, chess::PieceType::King))
+            .collect();
+
+        Some(match winner_pieces.as_slice() {
+            [] | [chess::PieceType::Knight] | [chess::PieceType::Bishop] => {
+                ClockResult::DrawInsufficientMaterial
+            }
+            _ => ClockResult::WinOnTime(winner),
+        })
+    }
+
+    fn print_clock(&self) {
+        if let Some(clock) = &self.clock {
+            println!(
+                "White: {:.1}s   Black: {:.1}s",
+                clock.remaining(Color::White).as_secs_f32(),
+                clock.remaining(Color::Black).as_secs_f32()
+            );
+        }
     }
 
     fn single_turn(&mut self, player: Color) {
+        if let Some(clock) = &mut self.clock {
+            clock.start(player);
+        }
+
         let chosen_move = match player {
             Color::Black => self.black_player.get_move(self.game()),
             Color::White => self.white_player.get_move(self.game()),
         };
 
         self.game.make_move(chosen_move).unwrap();
+
+        if let Some(clock) = &mut self.clock {
+            clock.stop();
+        }
+    }
+}
+
+/// Checks whether `game` has ended after the move just played: checkmate and stalemate are
+/// decided by whether the side now to move has any legal replies, the remaining draws by the
+/// fifty-move rule, threefold repetition, and insufficient material. Shared by `PlayLocal` and
+/// `PlayOnline` so both front ends agree on when a game is over.
+pub(crate) fn game_over(game: &Game) -> Option<GameResult> {
+    let side_to_move = game.side_to_move();
+
+    if game.legal_moves().is_empty() {
+        return Some(if game.is_king_checked(side_to_move) {
+            match side_to_move {
+                Color::White => GameResult::BlackWins,
+                Color::Black => GameResult::WhiteWins,
+            }
+        } else {
+            GameResult::Draw(DrawReason::Stalemate)
+        });
     }
+
+    if game.is_draw_by_fifty_moves() {
+        return Some(GameResult::Draw(DrawReason::FiftyMoveRule));
+    }
+    if game.is_draw_by_repetition() {
+        return Some(GameResult::Draw(DrawReason::ThreefoldRepetition));
+    }
+    if game.is_draw_by_insufficient_material() {
+        return Some(GameResult::Draw(DrawReason::InsufficientMaterial));
+    }
+
+    None
 }
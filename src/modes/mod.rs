@@ -1,9 +1,26 @@
 use std::{collections::HashMap, io, str::FromStr};
 
 use chess::{ai::Strategy, ChessIndex, ChessMove, Color, Game};
+pub use clock::{Clock, ClockConfig, ClockResult};
 pub use local::{PlayLocal, PlayLocalMode, PlayLocalOpts};
+pub use online::{OnlineError, PlayOnline, PlayOnlineOpts};
+pub use tui::TuiPlayer;
 
+mod clock;
 mod local;
+mod online;
+mod tui;
+
+/// The destination square of any `ChessMove` variant, used to key a move by where it lands
+/// (e.g. building a `to_index -> ChessMove` lookup from `Game::valid_moves_from`).
+pub(crate) fn move_destination(chess_move: &ChessMove) -> ChessIndex {
+    match chess_move {
+        ChessMove::Regular(rm) => rm.to_idx(),
+        ChessMove::Castle(cm) => cm.king_to(),
+        ChessMove::Promotion(pm) => pm.to_idx(),
+        ChessMove::EnPassant(epm) => epm.to_idx(),
+    }
+}
 
 pub trait Player {
     fn get_move(&self, game: &Game) -> ChessMove;
@@ -70,12 +87,7 @@ impl LocalPlayer {
             from_index,
             valid_moves
                 .into_iter()
-                .map(|m| match m {
-                    ChessMove::Regular(rm) => (rm.to_idx(), m),
-                    ChessMove::Castle(cm) => (cm.king_to(), m),
-                    ChessMove::Promotion(pm) => (pm.to_idx(), m),
-                    ChessMove::EnPassant(epm) => (epm.to_idx(), m),
-                })
+                .map(|m| (move_destination(&m), m))
                 .collect(),
         )
     }
@@ -101,39 +113,18 @@ impl LocalPlayer {
         }
     }
     fn print_highlighted(player: Color, game: &Game, highlighted: &[ChessMove]) {
+        let highlighted_squares = highlighted.iter().map(move_destination).collect();
         match player {
             Color::Black => {
                 println!(
                     "{}",
-                    chess::fmt::blacks_perspective(
-                        game.board(),
-                        &highlighted
-                            .iter()
-                            .map(|vm| match vm {
-                                ChessMove::Regular(rm) => rm.to_idx(),
-                                ChessMove::Castle(cm) => cm.king_to(),
-                                ChessMove::Promotion(pm) => pm.to_idx(),
-                                ChessMove::EnPassant(em) => em.to_idx(),
-                            })
-                            .collect(),
-                    )
+                    chess::fmt::blacks_perspective(game.board(), &highlighted_squares)
                 );
             }
             Color::White => {
                 println!(
                     "{}",
-                    chess::fmt::whites_perspective(
-                        game.board(),
-                        &highlighted
-                            .iter()
-                            .map(|vm| match vm {
-                                ChessMove::Regular(rm) => rm.to_idx(),
-                                ChessMove::Castle(cm) => cm.king_to(),
-                                ChessMove::Promotion(pm) => pm.to_idx(),
-                                ChessMove::EnPassant(em) => em.to_idx(),
-                            })
-                            .collect(),
-                    )
+                    chess::fmt::whites_perspective(game.board(), &highlighted_squares)
                 );
             }
         }
@@ -0,0 +1,237 @@
+use std::fmt::Display;
+
+use chess::{ChessMessage, ChessMove, Color, Game};
+use structopt::StructOpt;
+use tungstenite::{connect, stream::MaybeTlsStream, Message, WebSocket};
+
+use super::{
+    local::{game_over, GameResult},
+    Player,
+};
+
+#[derive(Debug, StructOpt)]
+pub struct PlayOnlineOpts {
+    /// `host:port` of the room server (`clichess-server`) to connect to.
+    server_addr: String,
+    /// Name of the room to join. The first player to name a room opens it and is seated White;
+    /// the second is seated Black.
+    room: String,
+    /// Open `room` if nobody has yet, instead of failing with `JoinRoomError::DoesntExist` when
+    /// it doesn't already exist. Pass this for the first player into a room, leave it off for
+    /// the second so a typo'd room name can't silently spin up its own empty room.
+    #[structopt(long)]
+    create: bool,
+}
+
+/// Why a `PlayOnline` session couldn't be established or kept alive.
+#[derive(Debug)]
+pub enum OnlineError {
+    /// The initial websocket handshake with the room server failed.
+    Connect(tungstenite::Error),
+    /// Sending or receiving a websocket message failed.
+    Io(tungstenite::Error),
+    /// The room server closed the connection before sending an expected message.
+    UnexpectedEof,
+    /// The room server rejected the join (room full, already started, or doesn't exist), or
+    /// reported a move as illegal.
+    Rejected(String),
+}
+
+impl Display for OnlineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let output = match self {
+            OnlineError::Connect(err) => format!("failed to connect to room server: {}", err),
+            OnlineError::Io(err) => format!("I/O error talking to room server: {}", err),
+            OnlineError::UnexpectedEof => "room server closed the connection".to_string(),
+            OnlineError::Rejected(reason) => format!("couldn't join room: {}", reason),
+        };
+        write!(f, "{}", output)
+    }
+}
+
+/// The online counterpart to `PlayLocal`: one local `Player` drives whichever seat the room
+/// server assigned, with the opponent's moves relayed over the same websocket `clichess-server`
+/// speaks to every connection, seated or spectating.
+pub struct PlayOnline<P> {
+    local_player: P,
+    my_color: Color,
+    socket: WebSocket<MaybeTlsStream<std::net::TcpStream>>,
+    game: Game,
+}
+
+impl<P> PlayOnline<P>
+where
+    P: Player,
+{
+    /// Connects to `opts.server_addr` and joins `opts.room`, opening it if `opts.create` is set,
+    /// blocking until the room server confirms which side was assigned.
+    ///
+    /// Speaks the same websocket handshake and `ChessMessage` JSON protocol as
+    /// `clichess-server`'s `WsConn`: the room's `#[get("/{room_id}")]` route, upgraded to a
+    /// websocket, with moves and board updates exchanged as JSON text frames.
+    pub fn join(opts: PlayOnlineOpts, local_player: P) -> Result<Self, OnlineError> {
+        let url = format!(
+            "ws://{}/{}?create={}",
+            opts.server_addr, opts.room, opts.create
+        );
+        let (socket, _response) = connect(url).map_err(OnlineError::Connect)?;
+
+        let mut online = Self {
+            local_player,
+            my_color: Color::White,
+            socket,
+            game: Game::new(),
+        };
+
+        // `WsConn::started` sends one plain-text frame as soon as a seat (or spectating slot)
+        // is assigned, before any `ChessMessage` JSON starts flowing. A seat's frame reads
+        // `seated <color> token <uuid>`.
+        let greeting = online.read_text()?;
+        let mut words = greeting.split_whitespace();
+        online.my_color = match (words.next(), words.next()) {
+            (Some("seated"), Some("White")) => Color::White,
+            (Some("seated"), Some("Black")) => Color::Black,
+            _ => return Err(OnlineError::Rejected(greeting.trim().to_string())),
+        };
+
+        Ok(online)
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn play(&mut self) -> Result<(), OnlineError> {
+        loop {
+            match self.my_color {
+                Color::White => crate::print_whites_perspective(self.game().board()),
+                Color::Black => crate::print_blacks_perspective(self.game().board()),
+            }
+
+            if self.game.current_player() == self.my_color {
+                self.send_local_move()?;
+            }
+            self.await_move()?;
+
+            if let Some(result) = game_over(&self.game) {
+                println!("{}", result);
+                return Ok(());
+            }
+        }
+    }
+
+    /// Asks the local `Player` for a move and sends it as a `ChessMessage::Move`. Doesn't apply
+    /// it locally yet: the room is the arbiter of legality, so the move only lands on `self.game`
+    /// once `await_move` sees it broadcast back.
+    fn send_local_move(&mut self) -> Result<(), OnlineError> {
+        let chosen_move = self.local_player.get_move(&self.game);
+        self.send_message(&to_chess_message(chosen_move))
+    }
+
+    /// Blocks until the room broadcasts the move whose turn it currently is, printing any
+    /// plain-text status line it sends (like "opponent disconnected") and skipping `BoardState`
+    /// snapshots, without acting on either. `clichess-server` only ever broadcasts a move it has already resolved
+    /// against its own `ChessBoard::legal_moves`, so resolving it again here against `self.game`
+    /// just finds the matching `ChessMove` rather than re-validating it.
+    fn await_move(&mut self) -> Result<(), OnlineError> {
+        loop {
+            match self.read_message()? {
+                ChessMessage::Move {
+                    from,
+                    to,
+                    promotion,
+                } => {
+                    let chess_move = self
+                        .game
+                        .valid_moves_from(from)
+                        .into_iter()
+                        .find(|m| matches_destination(m, to, promotion))
+                        .ok_or_else(|| {
+                            OnlineError::Rejected(format!(
+                                "room broadcast a move ({}{}) that isn't legal here",
+                                from, to
+                            ))
+                        })?;
+                    self.game.make_move(chess_move).unwrap();
+                    return Ok(());
+                }
+                ChessMessage::BoardState { .. } => continue,
+            }
+        }
+    }
+
+    /// Reads websocket frames until one parses as a `ChessMessage`, printing any plain-text
+    /// status line it skips over along the way.
+    fn read_message(&mut self) -> Result<ChessMessage, OnlineError> {
+        loop {
+            let text = self.read_text()?;
+            match serde_json::from_str(&text) {
+                Ok(message) => return Ok(message),
+                Err(_) => println!("{}", text),
+            }
+        }
+    }
+
+    fn read_text(&mut self) -> Result<String, OnlineError> {
+        loop {
+            match self.socket.read().map_err(OnlineError::Io)? {
+                Message::Text(text) => return Ok(text),
+                Message::Close(_) => return Err(OnlineError::UnexpectedEof),
+                Message::Ping(_) | Message::Pong(_) | Message::Binary(_) | Message::Frame(_) => {
+                    continue
+                }
+            }
+        }
+    }
+
+    fn send_message(&mut self, message: &ChessMessage) -> Result<(), OnlineError> {
+        let text = serde_json::to_string(message).expect("ChessMessage always serializes");
+        self.socket
+            .send(Message::Text(text))
+            .map_err(OnlineError::Io)
+    }
+}
+
+/// The `ChessMessage::Move` wire form of `chess_move`: the UCI-style `from`/`to`/`promotion`
+/// `clichess-server`'s `Lobby` resolves moves from, regardless of which `ChessMove` variant it
+/// actually is.
+fn to_chess_message(chess_move: ChessMove) -> ChessMessage {
+    match chess_move {
+        ChessMove::Regular(m) => ChessMessage::Move {
+            from: m.from_idx(),
+            to: m.to_idx(),
+            promotion: None,
+        },
+        ChessMove::Castle(m) => ChessMessage::Move {
+            from: m.king_from(),
+            to: m.king_to(),
+            promotion: None,
+        },
+        ChessMove::EnPassant(m) => ChessMessage::Move {
+            from: m.from_idx(),
+            to: m.to_idx(),
+            promotion: None,
+        },
+        ChessMove::Promotion(m) => ChessMessage::Move {
+            from: m.from_idx(),
+            to: m.to_idx(),
+            promotion: Some(m.promotion_piece()),
+        },
+    }
+}
+
+/// Whether `chess_move` is the move a `ChessMessage::Move { to, promotion, .. }` describes,
+/// mirroring how `clichess-server`'s `resolve_move` matches the same fields against
+/// `ChessBoard::legal_moves`.
+fn matches_destination(
+    chess_move: &ChessMove,
+    to: chess::ChessIndex,
+    promotion: Option<chess::PieceType>,
+) -> bool {
+    match chess_move {
+        ChessMove::Regular(m) => m.to_idx() == to && promotion.is_none(),
+        ChessMove::Promotion(m) => m.to_idx() == to && promotion == Some(m.promotion_piece()),
+        ChessMove::Castle(m) => m.king_to() == to && promotion.is_none(),
+        ChessMove::EnPassant(m) => m.to_idx() == to && promotion.is_none(),
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+use chess::{ChessIndex, ChessMove, Color, Game};
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode},
+    execute, queue,
+    terminal::{self, ClearType},
+};
+
+use super::{move_destination, Player};
+
+/// A raw-terminal front end for the same moves `LocalPlayer` offers: arrow keys walk a cursor
+/// around the board, Enter picks the hovered square up (or, once a piece is picked up, drops it
+/// on the hovered square), and Esc puts a picked-up piece back down. Legal destinations light up
+/// via the same `highlighted_squares` set `LocalPlayer` passes to `get_perspective`, so the two
+/// front ends render identically aside from how the player drives the cursor.
+pub struct TuiPlayer {}
+
+impl TuiPlayer {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Player for TuiPlayer {
+    fn get_move(&self, game: &Game) -> ChessMove {
+        let player = game.current_player();
+
+        terminal::enable_raw_mode().unwrap();
+        let mut stdout = io::stdout();
+        execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide).unwrap();
+
+        let chosen_move = run(&mut stdout, game, player);
+
+        execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen).unwrap();
+        terminal::disable_raw_mode().unwrap();
+
+        chosen_move
+    }
+}
+
+/// Nothing picked up yet, or a from-square and the destinations `valid_moves_from` allows from
+/// it, ready to be matched against wherever the cursor lands next.
+enum Selection {
+    Empty,
+    PickedUp(ChessIndex, Vec<ChessMove>),
+}
+
+fn run(stdout: &mut io::Stdout, game: &Game, player: Color) -> ChessMove {
+    let mut cursor_at: ChessIndex = match player {
+        Color::White => "e1".parse().unwrap(),
+        Color::Black => "e8".parse().unwrap(),
+    };
+    let mut selection = Selection::Empty;
+    let mut status = format!("{} to move", player);
+
+    loop {
+        render(stdout, game, player, cursor_at, &selection, &status);
+
+        let key_code = match event::read().unwrap() {
+            Event::Key(key_event) => key_event.code,
+            _ => continue,
+        };
+
+        match key_code {
+            KeyCode::Up => cursor_at = step(cursor_at, 0, 1),
+            KeyCode::Down => cursor_at = step(cursor_at, 0, -1),
+            KeyCode::Left => cursor_at = step(cursor_at, -1, 0),
+            KeyCode::Right => cursor_at = step(cursor_at, 1, 0),
+            KeyCode::Esc => {
+                if matches!(selection, Selection::PickedUp(_, _)) {
+                    selection = Selection::Empty;
+                    status = "put back down".to_string();
+                }
+            }
+            KeyCode::Enter => match &selection {
+                Selection::Empty => match game.board().piece_at(cursor_at) {
+                    Some(piece) if piece.color() == player => {
+                        let valid_moves = game.valid_moves_from(cursor_at);
+                        if valid_moves.is_empty() {
+                            status = format!("your {} on {} has no legal moves", piece, cursor_at);
+                        } else {
+                            status = format!("{} picked up, choose a destination", cursor_at);
+                            selection = Selection::PickedUp(cursor_at, valid_moves);
+                        }
+                    }
+                    _ => status = format!("no {} piece on {}", player, cursor_at),
+                },
+                Selection::PickedUp(from, valid_moves) => {
+                    if let Some(chosen_move) = valid_moves
+                        .iter()
+                        .find(|m| move_destination(m) == cursor_at)
+                    {
+                        return *chosen_move;
+                    } else if *from == cursor_at {
+                        selection = Selection::Empty;
+                        status = "put back down".to_string();
+                    } else {
+                        status = format!("{} isn't a legal destination from {}", cursor_at, from);
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+}
+
+/// Moves `idx` one square in the `(file_delta, rank_delta)` direction (each `-1`, `0`, or `1`),
+/// clamped to the edge of the board instead of wrapping.
+fn step(idx: ChessIndex, file_delta: i8, rank_delta: i8) -> ChessIndex {
+    let file = match file_delta {
+        1 => idx.file() + 1,
+        -1 => idx.file() - 1,
+        _ => Some(idx.file()),
+    };
+    let rank = match rank_delta {
+        1 => idx.rank() + 1,
+        -1 => idx.rank() - 1,
+        _ => Some(idx.rank()),
+    };
+
+    match (file, rank) {
+        (Some(file), Some(rank)) => ChessIndex::new(file, rank),
+        _ => idx,
+    }
+}
+
+/// Draws the board once (cursor square plus whatever `selection` lights up, through the same
+/// `highlighted_squares` set `LocalPlayer` passes to `get_perspective`) with the status line
+/// underneath, instead of reprinting "can't move to" text turn after turn.
+fn render(
+    stdout: &mut io::Stdout,
+    game: &Game,
+    player: Color,
+    cursor_at: ChessIndex,
+    selection: &Selection,
+    status: &str,
+) {
+    let mut highlighted_squares: HashSet<ChessIndex> = match selection {
+        Selection::Empty => HashSet::new(),
+        Selection::PickedUp(from, valid_moves) => {
+            let mut squares: HashSet<ChessIndex> =
+                valid_moves.iter().map(move_destination).collect();
+            squares.insert(*from);
+            squares
+        }
+    };
+    highlighted_squares.insert(cursor_at);
+
+    let board = match player {
+        Color::White => chess::fmt::whites_perspective(game.board(), &highlighted_squares),
+        Color::Black => chess::fmt::blacks_perspective(game.board(), &highlighted_squares),
+    };
+
+    queue!(stdout, cursor::MoveTo(0, 0), terminal::Clear(ClearType::All)).unwrap();
+    writeln!(stdout, "{}\r", board).unwrap();
+    writeln!(stdout, "cursor: {}\r", cursor_at).unwrap();
+    writeln!(stdout, "{}\r", status).unwrap();
+    stdout.flush().unwrap();
+}